@@ -27,11 +27,15 @@ use java_bindings::{
         objects::{JObject, JValue},
         JNIEnv, JavaVM,
     },
-    utils::{convert_to_string, get_class_name, jni_cache},
+    utils::{
+        byte_array, convert_to_string, get_class_name, jni_cache, with_local_frame,
+        DEFAULT_LOCAL_FRAME_CAPACITY,
+    },
     Executor, JniResult,
 };
 
 const EXECUTION_EXCEPTION_CLASS: &str = "com/exonum/binding/core/service/ExecutionException";
+const SERVICE_RUNTIME_ADAPTER_CLASS: &str = "com/exonum/binding/core/runtime/ServiceRuntimeAdapter";
 
 lazy_static! {
     pub static ref VM: Arc<JavaVM> = create_vm_for_benchmarks_with_classes();
@@ -106,3 +110,100 @@ pub fn get_class_name_not_cached(b: &mut Bencher) {
         })
         .unwrap();
 }
+
+#[bench]
+pub fn get_method_id_cached(b: &mut Bencher) {
+    EXECUTOR
+        .with_attached(|_env| {
+            b.iter(|| black_box(jni_cache::runtime_adapter::execute_tx_id()));
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[bench]
+pub fn get_method_id_not_cached(b: &mut Bencher) {
+    EXECUTOR
+        .with_attached(|env| {
+            b.iter(|| {
+                black_box(
+                    env.get_method_id(
+                        SERVICE_RUNTIME_ADAPTER_CLASS,
+                        "executeTransaction",
+                        "(ILjava/lang/String;I[BJI[B[B)V",
+                    )
+                    .unwrap(),
+                )
+            });
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[bench]
+pub fn convert_byte_array_1_mib(b: &mut Bencher) {
+    const PAYLOAD_SIZE: usize = 1024 * 1024;
+    EXECUTOR
+        .with_attached(|env| {
+            let array = env.byte_array_from_slice(&vec![1; PAYLOAD_SIZE])?;
+            b.iter(|| black_box(byte_array::to_vec(env, array).unwrap()));
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[bench]
+pub fn build_array_list_10k(b: &mut Bencher) {
+    use jni_cache::{
+        boxing::box_int,
+        collections::{list_add_all, new_array_list},
+    };
+    const LIST_SIZE: i32 = 10_000;
+    EXECUTOR
+        .with_attached(|env| {
+            // The frame releases all the local references created by an iteration,
+            // including the boxed elements if adding them fails.
+            b.iter(|| {
+                with_local_frame(env, DEFAULT_LOCAL_FRAME_CAPACITY, || {
+                    let list = new_array_list(env, LIST_SIZE)?;
+                    let elements = (0..LIST_SIZE).map(|i| box_int(env, i).unwrap());
+                    list_add_all(env, list, elements)?;
+                    black_box(list);
+                    Ok(())
+                })
+                .unwrap()
+            });
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[bench]
+pub fn byte_array_equals_critical_1_kib(b: &mut Bencher) {
+    const ARRAY_SIZE: usize = 1024;
+    EXECUTOR
+        .with_attached(|env| {
+            let first = env.byte_array_from_slice(&vec![1; ARRAY_SIZE])?;
+            let second = env.byte_array_from_slice(&vec![1; ARRAY_SIZE])?;
+            b.iter(|| black_box(byte_array::equals(env, first, second).unwrap()));
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[bench]
+pub fn byte_array_equals_copied_1_kib(b: &mut Bencher) {
+    const ARRAY_SIZE: usize = 1024;
+    EXECUTOR
+        .with_attached(|env| {
+            let first = env.byte_array_from_slice(&vec![1; ARRAY_SIZE])?;
+            let second = env.byte_array_from_slice(&vec![1; ARRAY_SIZE])?;
+            b.iter(|| {
+                let first = env.convert_byte_array(first).unwrap();
+                let second = env.convert_byte_array(second).unwrap();
+                black_box(first == second)
+            });
+            Ok(())
+        })
+        .unwrap();
+}
@@ -40,6 +40,12 @@ pub fn create_vm_for_benchmarks_with_classes() -> Arc<JavaVM> {
     Arc::new(create_vm(false, true))
 }
 
+/// Creates a configured `JavaVM` for tests without EJB classes.
+/// _`JavaVM` should be created only *once*._
+pub fn create_vm_for_tests() -> Arc<JavaVM> {
+    Arc::new(create_vm(true, false))
+}
+
 /// Creates a configured `JavaVM` for tests with EJB classes.
 /// _`JavaVM` should be created only *once*._
 pub fn create_vm_for_tests_with_classes() -> Arc<JavaVM> {
@@ -0,0 +1,96 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use integration_tests::vm::create_vm_for_tests;
+use java_bindings::{
+    jni::JavaVM,
+    utils::{
+        byte_array::{equals, to_vec},
+        ByteArrayError,
+    },
+    Executor,
+};
+use lazy_static::lazy_static;
+
+use std::{ptr, sync::Arc};
+
+lazy_static! {
+    static ref VM: Arc<JavaVM> = create_vm_for_tests();
+    pub static ref EXECUTOR: Executor = Executor::new(VM.clone());
+}
+
+#[test]
+fn equal_arrays() {
+    EXECUTOR
+        .with_attached(|env| {
+            let bytes: Vec<u8> = (0..=255).collect();
+            let a = env.byte_array_from_slice(&bytes)?;
+            let b = env.byte_array_from_slice(&bytes)?;
+            assert!(equals(env, a, b)?);
+            assert!(equals(env, a, a)?);
+
+            let empty_a = env.new_byte_array(0)?;
+            let empty_b = env.new_byte_array(0)?;
+            assert!(equals(env, empty_a, empty_b)?);
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn different_arrays() {
+    EXECUTOR
+        .with_attached(|env| {
+            let a = env.byte_array_from_slice(&[1, 2, 3])?;
+            let b = env.byte_array_from_slice(&[1, 2, 4])?;
+            let shorter = env.byte_array_from_slice(&[1, 2])?;
+            assert!(!equals(env, a, b)?);
+            assert!(!equals(env, a, shorter)?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn null_arrays() {
+    EXECUTOR
+        .with_attached(|env| {
+            let a = env.byte_array_from_slice(&[1])?;
+            assert!(equals(env, ptr::null_mut(), ptr::null_mut())?);
+            assert!(!equals(env, a, ptr::null_mut())?);
+            assert!(!equals(env, ptr::null_mut(), a)?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn copy_to_vec() {
+    EXECUTOR
+        .with_attached(|env| {
+            let bytes: Vec<u8> = (0..=255).collect();
+            let array = env.byte_array_from_slice(&bytes)?;
+            assert_eq!(to_vec(env, array).unwrap(), bytes);
+
+            let empty = env.new_byte_array(0)?;
+            assert_eq!(to_vec(env, empty).unwrap(), Vec::<u8>::new());
+            match to_vec(env, ptr::null_mut()) {
+                Err(ByteArrayError::NullByteArray) => {}
+                other => panic!("Unexpected result: {:?}", other),
+            }
+            Ok(())
+        })
+        .unwrap();
+}
@@ -0,0 +1,72 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use integration_tests::vm::create_vm_for_tests;
+use java_bindings::{
+    jni::{objects::JByteBuffer, JavaVM},
+    utils::byte_buffer::{from_direct, new_direct},
+    Executor,
+};
+use lazy_static::lazy_static;
+
+use std::sync::Arc;
+
+lazy_static! {
+    static ref VM: Arc<JavaVM> = create_vm_for_tests();
+    pub static ref EXECUTOR: Executor = Executor::new(VM.clone());
+}
+
+#[test]
+fn direct_buffer_round_trip() {
+    let mut data: Vec<u8> = (0..=255).collect();
+    EXECUTOR
+        .with_attached(|env| {
+            let buffer = unsafe { new_direct(env, data.as_mut_ptr(), data.len())? };
+            let capacity = env.call_method(buffer, "capacity", "()I", &[])?.i()?;
+            assert_eq!(capacity as usize, data.len());
+
+            // Modify the memory from Java and read it back.
+            env.call_method(
+                buffer,
+                "put",
+                "(IB)Ljava/nio/ByteBuffer;",
+                &[0.into(), 42i8.into()],
+            )?;
+            let view = unsafe { from_direct(env, buffer)? };
+            assert_eq!(view.len(), 256);
+            assert_eq!(view[0], 42);
+            assert_eq!(&view[1..], &(1..=255).collect::<Vec<u8>>()[..]);
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(data[0], 42);
+}
+
+#[test]
+fn heap_buffer_is_not_direct() {
+    EXECUTOR
+        .with_attached(|env| {
+            let buffer = env
+                .call_static_method(
+                    "java/nio/ByteBuffer",
+                    "allocate",
+                    "(I)Ljava/nio/ByteBuffer;",
+                    &[16.into()],
+                )?
+                .l()?;
+            assert!(unsafe { from_direct(env, JByteBuffer::from(buffer.into_inner())) }.is_err());
+            Ok(())
+        })
+        .unwrap();
+}
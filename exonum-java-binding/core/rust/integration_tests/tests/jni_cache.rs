@@ -13,13 +13,35 @@
 // limitations under the License.
 
 use integration_tests::vm::create_vm_for_tests_with_classes;
-use java_bindings::utils::jni_cache;
+use java_bindings::{
+    jni::{
+        objects::{GlobalRef, JClass, JMethodID, JObject, JStaticMethodID, JValue},
+        signature::{JavaType, Primitive},
+        JNIEnv, JavaVM,
+    },
+    utils::{
+        get_exception_cause, get_exception_message, is_assignable_from, is_instance_of_cached,
+        jni_cache::{
+            self, cached_entries, classes_refs, execution_exception, get_class_with_loader,
+            unexpected_execution_exception, CacheError, CachedEntry, CachedEntryKind,
+        },
+    },
+    Executor, JniResult,
+};
+use lazy_static::lazy_static;
 
 use std::{
     sync::{Arc, Barrier},
     thread::spawn,
 };
 
+lazy_static! {
+    static ref VM: Arc<JavaVM> = create_vm_for_tests_with_classes();
+    pub static ref EXECUTOR: Executor = Executor::new(VM.clone());
+}
+
+const EXECUTION_EXCEPTION_CLASS: &str = "com/exonum/binding/core/service/ExecutionException";
+
 #[test]
 // NOTE: This test is not supposed to reliably catch synchronization errors.
 fn concurrent_cache_read() {
@@ -27,7 +49,7 @@ fn concurrent_cache_read() {
     let mut threads = Vec::new();
 
     // Create a VM, initializing the JNI cache
-    let _jvm = create_vm_for_tests_with_classes();
+    lazy_static::initialize(&VM);
 
     let barrier = Arc::new(Barrier::new(THREAD_NUM));
 
@@ -44,8 +66,12 @@ fn concurrent_cache_read() {
             jni_cache::runtime_adapter::after_transactions_id();
             jni_cache::runtime_adapter::after_commit_id();
             jni_cache::runtime_adapter::shutdown_id();
+            jni_cache::blockchain_data::get_executing_service_data_id();
+            jni_cache::blockchain_data::get_access_native_handle_id();
             jni_cache::class::get_name_id();
             jni_cache::object::get_class_id();
+            jni_cache::static_methods::key_pair_new_instance_id();
+            jni_cache::fields::execution_exception_error_code_id();
             jni_cache::classes_refs::java_lang_error();
             jni_cache::classes_refs::execution_exception();
         });
@@ -55,3 +81,1251 @@ fn concurrent_cache_read() {
         jh.join().unwrap();
     }
 }
+
+// Every cached entry must be listed here, so that an entry declared but not cached
+// fails the test instead of panicking on the first use.
+#[test]
+fn all_cached_entries_are_set() {
+    use jni_cache::*;
+    lazy_static::initialize(&VM);
+
+    let try_method_ids: &[(&str, fn() -> Result<JMethodID<'static>, CacheError>)] = &[
+        ("<init>", runtime_adapter::try_constructor_id),
+        ("initialize", runtime_adapter::try_initialize_id),
+        ("deployArtifact", runtime_adapter::try_deploy_artifact_id),
+        (
+            "isArtifactDeployed",
+            runtime_adapter::try_is_artifact_deployed_id,
+        ),
+        (
+            "initiateAddingService",
+            runtime_adapter::try_initiate_adding_service_id,
+        ),
+        (
+            "initiateResumingService",
+            runtime_adapter::try_initiate_resuming_service_id,
+        ),
+        (
+            "updateServiceStatus",
+            runtime_adapter::try_update_service_status_id,
+        ),
+        ("executeTransaction", runtime_adapter::try_execute_tx_id),
+        (
+            "beforeTransactions",
+            runtime_adapter::try_before_transactions_id,
+        ),
+        (
+            "afterTransactions",
+            runtime_adapter::try_after_transactions_id,
+        ),
+        ("afterCommit", runtime_adapter::try_after_commit_id),
+        ("shutdown", runtime_adapter::try_shutdown_id),
+        (
+            "getExecutingServiceData",
+            blockchain_data::try_get_executing_service_data_id,
+        ),
+        ("getBlockchain", blockchain_data::try_get_blockchain_id),
+        (
+            "getAccessNativeHandle",
+            blockchain_data::try_get_access_native_handle_id,
+        ),
+        ("canModify", blockchain_data::try_can_modify_id),
+        ("getHeight", blockchain_data::try_get_height_id),
+        ("getLastBlock", blockchain_data::try_get_last_block_id),
+        ("getBlockHash", blockchain_data::try_get_block_hash_id),
+        ("asBytes", blockchain_data::try_as_bytes_id),
+        (
+            "getConsensusConfiguration",
+            blockchain_data::try_get_consensus_configuration_id,
+        ),
+    ];
+    for (name, try_method_id) in try_method_ids {
+        let method_id = try_method_id().unwrap_or_else(|e| panic!("{}: {}", name, e));
+        assert!(!method_id.into_inner().is_null(), "{}", name);
+    }
+
+    let method_ids: &[(&str, fn() -> JMethodID<'static>)] = &[
+        ("Object.getClass", object::get_class_id),
+        ("Object.toString", object::to_string_id),
+        ("AutoCloseable.close", auto_closeable::close_id),
+        ("Integer.intValue", boxing::int_value_id),
+        ("Long.longValue", boxing::long_value_id),
+        ("MessageLite.toByteArray", protobuf::to_byte_array_id),
+        ("ArrayList.<init>", collections::array_list_constructor_id),
+        ("List.add", collections::list_add_id),
+        ("List.size", collections::list_size_id),
+        ("Map.Entry.getKey", map_entry::get_key_id),
+        ("Map.Entry.getValue", map_entry::get_value_id),
+        ("CharSequence.length", char_sequence::length_id),
+        ("CharSequence.toString", char_sequence::to_string_id),
+        ("Method.getName", reflect::get_name_id),
+        ("Method.getParameterTypes", reflect::get_parameter_types_id),
+        (
+            "Thread.getContextClassLoader",
+            thread::get_context_class_loader_id,
+        ),
+        ("Class.getName", class::get_name_id),
+        ("Class.getSimpleName", class::get_simple_name_id),
+        ("Class.isAssignableFrom", class::is_assignable_from_id),
+        ("Throwable.getMessage", throwable::get_message_id),
+        (
+            "Throwable.getLocalizedMessage",
+            throwable::get_localized_message_id,
+        ),
+        ("Throwable.getCause", throwable::get_cause_id),
+        ("Throwable.getStackTrace", throwable::get_stack_trace_id),
+        (
+            "StackTraceElement.toString",
+            stack_trace_element::to_string_id,
+        ),
+        ("Throwable.addSuppressed", throwable::add_suppressed_id),
+        (
+            "ExecutionException.getErrorCode",
+            execution_exception::get_error_code_id,
+        ),
+        (
+            "ExecutionException.<init>",
+            execution_exception::constructor_id,
+        ),
+        (
+            "UnexpectedExecutionException.<init>",
+            unexpected_execution_exception::constructor_id,
+        ),
+    ];
+    for (name, method_id) in method_ids {
+        assert!(!method_id().into_inner().is_null(), "{}", name);
+    }
+
+    let static_method_ids: &[(&str, fn() -> JStaticMethodID<'static>)] = &[
+        ("Class.forName", static_methods::class_for_name_id),
+        (
+            "Thread.currentThread",
+            static_methods::thread_current_thread_id,
+        ),
+        (
+            "KeyPair.newInstance",
+            static_methods::key_pair_new_instance_id,
+        ),
+        ("System.nanoTime", static_methods::system_nano_time_id),
+        ("Integer.valueOf", static_methods::integer_value_of_id),
+        ("Long.valueOf", static_methods::long_value_of_id),
+    ];
+    for (name, method_id) in static_method_ids {
+        assert!(!method_id().into_inner().is_null(), "{}", name);
+    }
+
+    assert!(!fields::execution_exception_error_code_id()
+        .into_inner()
+        .is_null());
+
+    let classes: &[(&str, fn() -> GlobalRef)] = &[
+        ("Class", classes_refs::java_lang_class),
+        ("Error", classes_refs::java_lang_error),
+        ("System", classes_refs::java_lang_system),
+        ("Integer", classes_refs::java_lang_integer),
+        ("Long", classes_refs::java_lang_long),
+        ("ArrayList", classes_refs::java_util_array_list),
+        (
+            "RuntimeException",
+            classes_refs::java_lang_runtime_exception,
+        ),
+        (
+            "IllegalArgumentException",
+            classes_refs::java_lang_illegal_argument_exception,
+        ),
+        (
+            "IllegalStateException",
+            classes_refs::java_lang_illegal_state_exception,
+        ),
+        (
+            "NoSuchElementException",
+            classes_refs::java_util_no_such_element_exception,
+        ),
+        (
+            "IndexOutOfBoundsException",
+            classes_refs::java_lang_index_out_of_bounds_exception,
+        ),
+        ("ExecutionException", classes_refs::execution_exception),
+        (
+            "UnexpectedExecutionException",
+            classes_refs::unexpected_execution_exception,
+        ),
+        ("KeyPair", classes_refs::key_pair),
+        (
+            "ServiceRuntimeAdapter",
+            classes_refs::service_runtime_adapter,
+        ),
+    ];
+    for (name, class) in classes {
+        assert!(!class().as_obj().is_null(), "{}", name);
+    }
+}
+
+#[test]
+fn get_class_with_system_loader() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let loader = system_class_loader(env)?;
+            let class = get_class_with_loader(env, EXECUTION_EXCEPTION_CLASS, loader).unwrap();
+            assert!(
+                env.is_same_object(class.as_obj(), classes_refs::execution_exception().as_obj())?
+            );
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn warm_service_classes_with_system_loader() {
+    use jni_cache::service_classes::{
+        release_service_classes, service_class, warm_service_classes,
+    };
+
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let loader = system_class_loader(env)?;
+            let classes = [EXECUTION_EXCEPTION_CLASS.to_owned()];
+            warm_service_classes(env, 1, &classes, loader).unwrap();
+            let class = service_class(1, EXECUTION_EXCEPTION_CLASS).unwrap();
+            assert!(
+                env.is_same_object(class.as_obj(), classes_refs::execution_exception().as_obj())?
+            );
+            assert!(service_class(2, EXECUTION_EXCEPTION_CLASS).is_none());
+
+            // Nothing is cached if any of the classes is not found.
+            let classes = [
+                "java/lang/String".to_owned(),
+                "com/exonum/binding/Unknown".to_owned(),
+            ];
+            assert!(warm_service_classes(env, 2, &classes, loader).is_err());
+            assert!(!env.exception_check()?);
+            assert!(service_class(2, "java/lang/String").is_none());
+
+            assert_eq!(release_service_classes(1), 1);
+            assert!(service_class(1, EXECUTION_EXCEPTION_CLASS).is_none());
+            assert_eq!(release_service_classes(1), 0);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn cache_custom_method_table() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let ids = jni_cache::cache_custom_methods(
+                env,
+                &[
+                    ("java/lang/String", "length", "()I"),
+                    ("java/lang/String", "isEmpty", "()Z"),
+                ],
+            )
+            .unwrap();
+            assert_eq!(ids.len(), 2);
+
+            let string = JObject::from(env.new_string("test")?);
+            let length = env
+                .call_method_unchecked(string, ids[0], JavaType::Primitive(Primitive::Int), &[])?
+                .i()?;
+            assert_eq!(length, 4);
+            let is_empty = env
+                .call_method_unchecked(
+                    string,
+                    ids[1],
+                    JavaType::Primitive(Primitive::Boolean),
+                    &[],
+                )?
+                .z()?;
+            assert!(!is_empty);
+
+            let err = jni_cache::cache_custom_methods(
+                env,
+                &[
+                    ("java/lang/String", "length", "()I"),
+                    ("java/lang/String", "unknown", "()V"),
+                ],
+            )
+            .unwrap_err();
+            match err {
+                CacheError::MethodNotFound { name, .. } => assert_eq!(name, "unknown"),
+                _ => panic!("Unexpected error: {}", err),
+            }
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn get_class_with_loader_not_found() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let loader = system_class_loader(env)?;
+            let err = get_class_with_loader(env, "com/exonum/binding/Unknown", loader).unwrap_err();
+            match err {
+                CacheError::ClassNotFound { class, loader } => {
+                    assert_eq!(class, "com/exonum/binding/Unknown");
+                    assert!(loader.contains("AppClassLoader"), "{}", loader);
+                }
+                _ => panic!("Unexpected error: {}", err),
+            }
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn cached_entries_describe_cache() {
+    lazy_static::initialize(&VM);
+
+    let entries = cached_entries();
+    assert!(entries.contains(&CachedEntry {
+        class: "com/exonum/binding/core/runtime/ServiceRuntimeAdapter".to_owned(),
+        name: "executeTransaction".to_owned(),
+        sig: "(ILjava/lang/String;I[BJI[B[B)V".to_owned(),
+        kind: CachedEntryKind::Method,
+    }));
+    assert!(entries.contains(&CachedEntry {
+        class: EXECUTION_EXCEPTION_CLASS.to_owned(),
+        name: String::new(),
+        sig: String::new(),
+        kind: CachedEntryKind::Class,
+    }));
+}
+
+#[test]
+fn self_test_resolves_all_entries() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let report = jni_cache::self_test(env);
+            let total = cached_entries().len();
+            assert!(
+                report.starts_with(&format!(
+                    "JNI cache self-test: {} of {} entries resolved",
+                    total, total
+                )),
+                "{}",
+                report
+            );
+            assert!(!report.contains("FAILED"), "{}", report);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn repeated_init_within_same_loader_is_not_mismatch() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            jni_cache::try_init_cache(env).unwrap();
+            assert!(!jni_cache::loader_mismatch_detected());
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn read_error_code_of_execution_exception() {
+    const ERROR_CODE: i8 = 42;
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let exception =
+                execution_exception::new_execution_exception(env, ERROR_CODE, "Test error")?;
+            let error_code = execution_exception::read_error_code(env, exception.into())?;
+            assert_eq!(error_code, ERROR_CODE);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn read_error_code_of_null_exception() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let result = execution_exception::read_error_code(env, JObject::null());
+            assert!(result.is_err());
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn execution_exception_to_execution_error() {
+    use execution_exception::{to_execution_error, ExecutionError};
+
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let exception = execution_exception::new_execution_exception(env, 42, "Test error")?;
+            assert_eq!(
+                to_execution_error(env, exception.into())?,
+                ExecutionError {
+                    code: 42,
+                    description: "Test error".to_owned(),
+                }
+            );
+
+            let without_message = env.new_object(
+                "com/exonum/binding/core/service/ExecutionException",
+                "(B)V",
+                &[7i8.into()],
+            )?;
+            let error = to_execution_error(env, without_message)?;
+            assert_eq!(error.code, 7);
+            assert_eq!(error.description, "");
+
+            let not_execution_exception = env.new_object("java/lang/Exception", "()V", &[])?;
+            let error = to_execution_error(env, not_execution_exception).unwrap_err();
+            assert!(
+                error.to_string().contains("java.lang.Exception"),
+                "{}",
+                error
+            );
+
+            assert!(to_execution_error(env, JObject::null()).is_err());
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn throw_unexpected_execution_exception() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            unexpected_execution_exception::throw_unexpected_execution_exception(
+                env,
+                "Native failure",
+            )?;
+            let exception = env.exception_occurred()?;
+            env.exception_clear()?;
+            assert!(env.is_instance_of(
+                exception,
+                &classes_refs::unexpected_execution_exception()
+            )?);
+            let cause = get_exception_cause(env, exception.into())?;
+            let message = get_exception_message(env, cause)?;
+            assert_eq!(message, Some("Native failure".to_owned()));
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn java_vm_is_cached() {
+    lazy_static::initialize(&VM);
+
+    assert_eq!(
+        jni_cache::java_vm().get_java_vm_pointer(),
+        VM.get_java_vm_pointer()
+    );
+}
+
+#[test]
+fn with_attached_thread_attaches_native_thread() {
+    lazy_static::initialize(&VM);
+
+    spawn(|| {
+        jni_cache::with_attached_thread(|env| {
+            assert!(env.get_version().is_ok());
+            // The thread is attached as a daemon
+            let thread = env
+                .call_static_method(
+                    "java/lang/Thread",
+                    "currentThread",
+                    "()Ljava/lang/Thread;",
+                    &[],
+                )
+                .and_then(|thread| thread.l())
+                .unwrap();
+            let is_daemon = env.call_method(thread, "isDaemon", "()Z", &[]);
+            assert!(is_daemon.and_then(|daemon| daemon.z()).unwrap());
+        });
+        // The thread is detached after the closure returns
+        assert!(VM.get_env().is_err());
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn with_attached_thread_detaches_on_panic() {
+    lazy_static::initialize(&VM);
+
+    spawn(|| {
+        let result = std::panic::catch_unwind(|| {
+            jni_cache::with_attached_thread(|_| panic!("Closure panicked"))
+        });
+        assert!(result.is_err());
+        assert!(VM.get_env().is_err());
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn with_attached_thread_keeps_attached_thread() {
+    lazy_static::initialize(&VM);
+
+    spawn(|| {
+        let _guard = VM.attach_current_thread().unwrap();
+        let method_id = jni_cache::with_attached_thread(|env| {
+            assert!(env.get_version().is_ok());
+            jni_cache::object::to_string_id()
+        });
+        assert!(!method_id.into_inner().is_null());
+        // The thread attached before the call remains attached
+        assert!(VM.get_env().is_ok());
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn current_context_loader_of_attached_thread() {
+    EXECUTOR
+        .with_attached(|env| {
+            let context_loader = jni_cache::thread::current_context_loader(env)?;
+            // Native threads attached to the JVM use the system class loader.
+            assert!(env.is_same_object(context_loader, system_class_loader(env)?)?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn lazy_method_resolved_on_first_access() {
+    lazy_static::initialize(&VM);
+
+    let method_id = jni_cache::runtime_adapter::try_initiate_resuming_service_id().unwrap();
+    assert_eq!(
+        jni_cache::runtime_adapter::initiate_resuming_service_id().into_inner(),
+        method_id.into_inner()
+    );
+    assert!(cached_entries()
+        .iter()
+        .any(|entry| entry.name == "initiateResumingService"));
+}
+
+#[test]
+fn is_instance_of_cached_classes() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let exception = execution_exception::new_execution_exception(env, 1, "Test")?;
+            let exception = JObject::from(exception);
+            assert!(is_instance_of_cached(
+                env,
+                exception,
+                &classes_refs::execution_exception()
+            ));
+            // ExecutionException extends RuntimeException
+            assert!(is_instance_of_cached(
+                env,
+                exception,
+                &classes_refs::java_lang_runtime_exception()
+            ));
+            assert!(!is_instance_of_cached(
+                env,
+                exception,
+                &classes_refs::java_lang_error()
+            ));
+            assert!(!is_instance_of_cached(
+                env,
+                JObject::null(),
+                &classes_refs::execution_exception()
+            ));
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn is_assignable_from_cached_classes() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let execution_exception = classes_refs::execution_exception();
+            let runtime_exception = classes_refs::java_lang_runtime_exception();
+            let execution_exception = JClass::from(execution_exception.as_obj());
+            let runtime_exception = JClass::from(runtime_exception.as_obj());
+            assert!(is_assignable_from(
+                env,
+                execution_exception,
+                runtime_exception
+            )?);
+            assert!(is_assignable_from(
+                env,
+                execution_exception,
+                execution_exception
+            )?);
+            assert!(!is_assignable_from(
+                env,
+                runtime_exception,
+                execution_exception
+            )?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn all_exception_classes_list_subclasses_first() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let all = classes_refs::all();
+            let names: Vec<&str> = all.iter().map(|(name, _)| *name).collect();
+            assert!(names.contains(&EXECUTION_EXCEPTION_CLASS));
+            assert_eq!(names.last(), Some(&"java/lang/Error"));
+
+            for (i, (name, class)) in all.iter().enumerate() {
+                let class = JClass::from(class.as_obj());
+                assert!(env.is_same_object(env.find_class(*name)?, class)?);
+                for (later_name, later_class) in &all[i + 1..] {
+                    let later_class = JClass::from(later_class.as_obj());
+                    assert!(
+                        !is_assignable_from(env, later_class, class)?,
+                        "{} precedes its subclass {}",
+                        name,
+                        later_name
+                    );
+                }
+            }
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn close_quietly_closes_object() {
+    EXECUTOR
+        .with_attached(|env| {
+            let input = env.new_object(
+                "java/io/StringReader",
+                "(Ljava/lang/String;)V",
+                &[JObject::from(env.new_string("a")?).into()],
+            )?;
+            jni_cache::auto_closeable::close_quietly(env, input);
+            assert!(!env.exception_check()?);
+
+            // A closed reader throws on `read`.
+            assert!(env.call_method(input, "read", "()I", &[]).is_err());
+            env.exception_clear()?;
+
+            // Closing is idempotent and null objects are ignored.
+            jni_cache::auto_closeable::close_quietly(env, input);
+            jni_cache::auto_closeable::close_quietly(env, JObject::null());
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn close_quietly_suppresses_close_failure_during_propagation() {
+    EXECUTOR
+        .with_attached(|env| {
+            let writer = failing_on_close_writer(env)?;
+            env.throw_new("java/lang/IllegalStateException", "Primary")?;
+            jni_cache::auto_closeable::close_quietly(env, writer);
+
+            // The primary exception is kept pending, with the close failure attached to it.
+            assert!(env.exception_check()?);
+            let primary = env.exception_occurred()?;
+            env.exception_clear()?;
+            assert!(env.is_instance_of(primary, "java/lang/IllegalStateException")?);
+            let suppressed = env
+                .call_method(primary, "getSuppressed", "()[Ljava/lang/Throwable;", &[])?
+                .l()?;
+            assert_eq!(env.get_array_length(suppressed.into_inner())?, 1);
+            let secondary = env.get_object_array_element(suppressed.into_inner(), 0)?;
+            assert!(env.is_instance_of(secondary, "java/io/IOException")?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn close_quietly_clears_close_failure() {
+    EXECUTOR
+        .with_attached(|env| {
+            let writer = failing_on_close_writer(env)?;
+            jni_cache::auto_closeable::close_quietly(env, writer);
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn execute_transaction_passes_args_to_adapter() {
+    use jni_cache::runtime_adapter::{execute_transaction, ExecuteTxArgs};
+
+    EXECUTOR
+        .with_attached(|env| {
+            // The adapter is allocated without calling its constructor, so the call reaches
+            // `executeTransaction` with the passed arguments and fails on its null fields.
+            let adapter = env.alloc_object(&classes_refs::service_runtime_adapter())?;
+            let args = ExecuteTxArgs {
+                service_id: 1,
+                interface_name: "",
+                tx_id: 2,
+                arguments: &[1, 2, 3],
+                blockchain_data_handle: 0,
+                caller_id: 0,
+                message_hash: &[0; 32],
+                author_pk: &[0; 32],
+            };
+            let result = execute_transaction(env, adapter, args);
+            assert!(result.is_err());
+
+            let exception = env.exception_occurred()?;
+            env.exception_clear()?;
+            assert!(env.is_instance_of(exception, "java/lang/NullPointerException")?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn execute_transaction_rejects_interface_name_with_nul() {
+    use jni_cache::runtime_adapter::{execute_transaction, ExecuteTxArgs, ExecuteTxError};
+
+    EXECUTOR
+        .with_attached(|env| {
+            let adapter = env.alloc_object(&classes_refs::service_runtime_adapter())?;
+            let args = ExecuteTxArgs {
+                service_id: 1,
+                interface_name: "exonum.\0Configure",
+                tx_id: 2,
+                arguments: &[],
+                blockchain_data_handle: 0,
+                caller_id: 0,
+                message_hash: &[0; 32],
+                author_pk: &[0; 32],
+            };
+            match execute_transaction(env, adapter, args) {
+                Err(ExecuteTxError::InvalidInterfaceName(name)) => {
+                    assert_eq!(name, "exonum.\0Configure")
+                }
+                other => panic!("Unexpected result: {:?}", other),
+            }
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn native_library_is_compatible_with_jar() {
+    use java_bindings::verify_version_compatibility;
+
+    EXECUTOR
+        .with_attached(|env| {
+            let adapter = env.alloc_object(&classes_refs::service_runtime_adapter())?;
+            verify_version_compatibility(env, adapter).unwrap();
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn register_adapters_by_runtime_id() {
+    use java_bindings::utils::ref_metrics::new_global_ref;
+    use jni_cache::adapters::{adapter, clear, register_adapter, unregister_adapter};
+
+    EXECUTOR
+        .with_attached(|env| {
+            let first = new_global_ref(
+                env,
+                env.alloc_object(&classes_refs::service_runtime_adapter())?,
+            )?;
+            let second = new_global_ref(
+                env,
+                env.alloc_object(&classes_refs::service_runtime_adapter())?,
+            )?;
+            assert!(register_adapter(1, first.clone()).is_none());
+            assert!(register_adapter(2, second.clone()).is_none());
+
+            let registered = adapter(1).unwrap();
+            assert!(env.is_same_object(registered.as_obj(), first.as_obj())?);
+            let registered = adapter(2).unwrap();
+            assert!(env.is_same_object(registered.as_obj(), second.as_obj())?);
+            assert!(adapter(3).is_none());
+
+            assert!(unregister_adapter(1).is_some());
+            assert!(adapter(1).is_none());
+
+            clear();
+            assert!(adapter(2).is_none());
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn is_adapter_checks_class() {
+    use jni_cache::runtime_adapter::is_adapter;
+
+    EXECUTOR
+        .with_attached(|env| {
+            let adapter = env.alloc_object(&classes_refs::service_runtime_adapter())?;
+            assert!(is_adapter(env, adapter));
+            let other = env.new_string("Not an adapter")?;
+            assert!(!is_adapter(env, other.into()));
+            assert!(!is_adapter(env, JObject::null()));
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn new_adapter_instantiates_adapter() {
+    EXECUTOR
+        .with_attached(|env| {
+            let adapter =
+                jni_cache::runtime_adapter::new_adapter(env, JObject::null(), JObject::null())?;
+            assert!(jni_cache::runtime_adapter::is_adapter(
+                env,
+                adapter.as_obj()
+            ));
+            let service_runtime = env
+                .call_method(
+                    adapter.as_obj(),
+                    "getServiceRuntime",
+                    "()Lcom/exonum/binding/core/runtime/ServiceRuntime;",
+                    &[],
+                )?
+                .l()?;
+            assert!(service_runtime.is_null());
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn intern_string_returns_same_string() {
+    use jni_cache::strings::{intern_string, known_string};
+
+    EXECUTOR
+        .with_attached(|env| {
+            let first = intern_string(env, "exonum.Configure")?;
+            let second = intern_string(env, "exonum.Configure")?;
+            assert!(env.is_same_object(first.as_obj(), second.as_obj())?);
+            let contents: String = env.get_string(first.as_obj().into())?.into();
+            assert_eq!(contents, "exonum.Configure");
+
+            // Strings outside the interned set are created anew.
+            let first = intern_string(env, "test.Interface")?;
+            let second = intern_string(env, "test.Interface")?;
+            assert!(!env.is_same_object(first.as_obj(), second.as_obj())?);
+
+            assert_eq!(known_string(""), Some(""));
+            assert_eq!(known_string("test.Interface"), None);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn serialize_execution_error_round_trip() {
+    const EXECUTION_ERROR: &str = "com/exonum/messages/core/runtime/Errors$ExecutionError";
+    const BUILDER: &str = "com/exonum/messages/core/runtime/Errors$ExecutionError$Builder";
+
+    EXECUTOR
+        .with_attached(|env| {
+            let builder = env
+                .call_static_method(
+                    EXECUTION_ERROR,
+                    "newBuilder",
+                    format!("()L{};", BUILDER),
+                    &[],
+                )?
+                .l()?;
+            env.call_method(
+                builder,
+                "setCode",
+                format!("(I)L{};", BUILDER),
+                &[42.into()],
+            )?;
+            let error = env
+                .call_method(builder, "build", format!("()L{};", EXECUTION_ERROR), &[])?
+                .l()?;
+
+            let bytes = jni_cache::protobuf::serialize_execution_error(env, error)?;
+            let bytes = env.byte_array_from_slice(&bytes)?;
+            let parsed = env
+                .call_static_method(
+                    EXECUTION_ERROR,
+                    "parseFrom",
+                    format!("([B)L{};", EXECUTION_ERROR),
+                    &[JObject::from(bytes).into()],
+                )?
+                .l()?;
+            assert_eq!(env.call_method(parsed, "getCode", "()I", &[])?.i()?, 42);
+
+            assert!(jni_cache::protobuf::serialize_execution_error(env, JObject::null()).is_err());
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn build_array_list() {
+    use jni_cache::{
+        boxing::{box_int, int_value},
+        collections::{list_add, list_add_all, list_size, new_array_list},
+    };
+
+    EXECUTOR
+        .with_attached(|env| {
+            let list = new_array_list(env, 4)?;
+            assert_eq!(list_size(env, list)?, 0);
+
+            list_add(env, list, box_int(env, 0)?)?;
+            let elements: JniResult<Vec<JObject>> = (1..10).map(|i| box_int(env, i)).collect();
+            list_add_all(env, list, elements?)?;
+            assert_eq!(list_size(env, list)?, 10);
+
+            let last = env
+                .call_method(list, "get", "(I)Ljava/lang/Object;", &[9.into()])?
+                .l()?;
+            assert_eq!(int_value(env, last)?, 9);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn read_map_entries() {
+    use jni_cache::{
+        boxing::{box_int, int_value},
+        map_entry::read_entry,
+    };
+
+    EXECUTOR
+        .with_attached(|env| {
+            let map = env.new_object("java/util/TreeMap", "()V", &[])?;
+            for i in 0..3 {
+                env.call_method(
+                    map,
+                    "put",
+                    "(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;",
+                    &[box_int(env, i)?.into(), box_int(env, i * 10)?.into()],
+                )?;
+            }
+            let entries = env
+                .call_method(map, "entrySet", "()Ljava/util/Set;", &[])?
+                .l()?;
+            let iterator = env
+                .call_method(entries, "iterator", "()Ljava/util/Iterator;", &[])?
+                .l()?;
+
+            let mut read = Vec::new();
+            while env.call_method(iterator, "hasNext", "()Z", &[])?.z()? {
+                let entry = env
+                    .call_method(iterator, "next", "()Ljava/lang/Object;", &[])?
+                    .l()?;
+                let (key, value) = read_entry(env, entry)?;
+                read.push((int_value(env, key)?, int_value(env, value)?));
+            }
+            assert_eq!(read, vec![(0, 0), (1, 10), (2, 20)]);
+
+            assert!(read_entry(env, JObject::null()).is_err());
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn describe_reflected_method() {
+    use jni_cache::reflect::{describe_method, method_name, parameter_type_names};
+
+    EXECUTOR
+        .with_attached(|env| {
+            let string_class = env.find_class("java/lang/String")?;
+            let parameter_types = env.new_object_array(2, "java/lang/Class", JObject::null())?;
+            let int_class = env
+                .get_static_field("java/lang/Integer", "TYPE", "Ljava/lang/Class;")?
+                .l()?;
+            env.set_object_array_element(parameter_types, 0, int_class)?;
+            env.set_object_array_element(parameter_types, 1, int_class)?;
+            let method = env
+                .call_method(
+                    string_class,
+                    "getMethod",
+                    "(Ljava/lang/String;[Ljava/lang/Class;)Ljava/lang/reflect/Method;",
+                    &[
+                        JObject::from(env.new_string("substring")?).into(),
+                        JObject::from(parameter_types).into(),
+                    ],
+                )?
+                .l()?;
+
+            assert_eq!(method_name(env, method)?, "substring");
+            assert_eq!(parameter_type_names(env, method)?, vec!["int", "int"]);
+            assert_eq!(describe_method(env, method)?, "substring(int, int)");
+            assert!(describe_method(env, JObject::null()).is_err());
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn char_sequence_to_rust_string() {
+    use jni_cache::char_sequence::{length, to_rust_string};
+
+    EXECUTOR
+        .with_attached(|env| {
+            let string = env.new_string("Exonum ✓")?;
+            assert_eq!(to_rust_string(env, string.into())?, "Exonum ✓");
+            assert_eq!(length(env, string.into())?, 8);
+
+            let builder = env.new_object(
+                "java/lang/StringBuilder",
+                "(Ljava/lang/CharSequence;)V",
+                &[string.into()],
+            )?;
+            assert_eq!(to_rust_string(env, builder)?, "Exonum ✓");
+            assert_eq!(length(env, builder)?, 8);
+
+            assert!(to_rust_string(env, JObject::null()).is_err());
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn system_nano_time_is_monotonic() {
+    EXECUTOR
+        .with_attached(|env| {
+            let start = jni_cache::system::nano_time(env)?;
+            let end = jni_cache::system::nano_time(env)?;
+            assert!(end >= start);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn adapter_contract_is_satisfied() {
+    EXECUTOR
+        .with_attached(|env| {
+            jni_cache::verify_adapter_contract(env).unwrap();
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn boxing_round_trip() {
+    use jni_cache::boxing::{box_int, box_long, int_value, long_value};
+
+    EXECUTOR
+        .with_attached(|env| {
+            for &value in &[0, 1, -1, i32::MIN, i32::MAX] {
+                let boxed = box_int(env, value)?;
+                assert!(env.is_instance_of(boxed, "java/lang/Integer")?);
+                assert_eq!(int_value(env, boxed)?, value);
+            }
+            for &value in &[0, 1, -1, i64::MIN, i64::MAX] {
+                let boxed = box_long(env, value)?;
+                assert!(env.is_instance_of(boxed, "java/lang/Long")?);
+                assert_eq!(long_value(env, boxed)?, value);
+            }
+            assert!(int_value(env, JObject::null()).is_err());
+            assert!(long_value(env, JObject::null()).is_err());
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn boxed_booleans_are_cached_singletons() {
+    EXECUTOR
+        .with_attached(|env| {
+            for &value in &[true, false] {
+                let boxed = jni_cache::boxing::boolean(env, value)?;
+                let expected = env
+                    .call_static_method(
+                        "java/lang/Boolean",
+                        "valueOf",
+                        "(Z)Ljava/lang/Boolean;",
+                        &[JValue::from(value)],
+                    )?
+                    .l()?;
+                assert!(env.is_same_object(boxed, expected)?);
+                let unboxed = env.call_method(boxed, "booleanValue", "()Z", &[])?.z()?;
+                assert_eq!(unboxed, value);
+            }
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn class_name_of_object() {
+    EXECUTOR
+        .with_attached(|env| {
+            let string = env.new_string("test")?;
+            assert_eq!(
+                jni_cache::object::class_name(env, string.into())?,
+                "java.lang.String"
+            );
+            let boxed = jni_cache::boxing::box_long(env, 1)?;
+            assert_eq!(jni_cache::object::class_name(env, boxed)?, "java.lang.Long");
+
+            assert!(jni_cache::object::class_name(env, JObject::null()).is_err());
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn class_name_of_global_ref_on_native_thread() {
+    let string = EXECUTOR
+        .with_attached(|env| {
+            let string = env.new_string("test")?;
+            env.new_global_ref(string)
+        })
+        .unwrap();
+
+    spawn(move || {
+        let class_name = jni_cache::object::class_name_on_current_thread(string.as_obj());
+        assert_eq!(class_name.unwrap(), "java.lang.String");
+        // The thread is detached after the call
+        assert!(VM.get_env().is_err());
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn block_hash_of_block() {
+    EXECUTOR
+        .with_attached(|env| {
+            let block_hash = [1; 32];
+            let block = new_block(env, &block_hash)?;
+            assert_eq!(
+                jni_cache::blockchain_data::block_hash(env, block)?,
+                block_hash.to_vec()
+            );
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn last_cache_duration_is_recorded() {
+    lazy_static::initialize(&VM);
+
+    assert!(jni_cache::last_cache_duration().is_some());
+}
+
+fn system_class_loader<'a>(env: &JNIEnv<'a>) -> JniResult<JObject<'a>> {
+    env.call_static_method(
+        "java/lang/ClassLoader",
+        "getSystemClassLoader",
+        "()Ljava/lang/ClassLoader;",
+        &[],
+    )?
+    .l()
+}
+
+/// Returns a `Writer` whose `close` throws `IOException`, as it flushes the buffered
+/// characters to a not connected pipe.
+fn failing_on_close_writer<'a>(env: &JNIEnv<'a>) -> JniResult<JObject<'a>> {
+    let pipe = env.new_object("java/io/PipedWriter", "()V", &[])?;
+    let writer = env.new_object(
+        "java/io/BufferedWriter",
+        "(Ljava/io/Writer;)V",
+        &[JValue::from(pipe)],
+    )?;
+    env.call_method(writer, "write", "(I)V", &[JValue::from('a' as i32)])?;
+    Ok(writer)
+}
+
+/// Creates a `Block` with the given hash (and zero hashes otherwise).
+fn new_block<'a>(env: &JNIEnv<'a>, block_hash: &[u8]) -> JniResult<JObject<'a>> {
+    const HASH_CODE_CLASS: &str = "com/exonum/binding/common/hash/HashCode";
+    const BUILDER_SIG: &str = "Lcom/exonum/binding/core/blockchain/Block$Builder;";
+    let new_hash = |bytes: &[u8]| -> JniResult<JObject<'a>> {
+        let bytes = JObject::from(env.byte_array_from_slice(bytes)?);
+        env.call_static_method(
+            HASH_CODE_CLASS,
+            "fromBytes",
+            "([B)Lcom/exonum/binding/common/hash/HashCode;",
+            &[JValue::from(bytes)],
+        )?
+        .l()
+    };
+    let zero_hash = new_hash(&[0; 32])?;
+    let headers = env
+        .call_static_method(
+            "com/google/common/collect/ImmutableMap",
+            "of",
+            "()Lcom/google/common/collect/ImmutableMap;",
+            &[],
+        )?
+        .l()?;
+    let mut builder = env
+        .call_static_method(
+            "com/exonum/binding/core/blockchain/Block",
+            "builder",
+            &format!("(){}", BUILDER_SIG),
+            &[],
+        )?
+        .l()?;
+    let setters: [(&str, String, JValue); 9] = [
+        (
+            "blockHash",
+            format!("(L{};){}", HASH_CODE_CLASS, BUILDER_SIG),
+            JValue::from(new_hash(block_hash)?),
+        ),
+        ("proposerId", format!("(I){}", BUILDER_SIG), JValue::from(0)),
+        ("height", format!("(J){}", BUILDER_SIG), JValue::from(1_i64)),
+        (
+            "numTransactions",
+            format!("(I){}", BUILDER_SIG),
+            JValue::from(0),
+        ),
+        (
+            "previousBlockHash",
+            format!("(L{};){}", HASH_CODE_CLASS, BUILDER_SIG),
+            JValue::from(zero_hash),
+        ),
+        (
+            "txRootHash",
+            format!("(L{};){}", HASH_CODE_CLASS, BUILDER_SIG),
+            JValue::from(zero_hash),
+        ),
+        (
+            "stateHash",
+            format!("(L{};){}", HASH_CODE_CLASS, BUILDER_SIG),
+            JValue::from(zero_hash),
+        ),
+        (
+            "errorHash",
+            format!("(L{};){}", HASH_CODE_CLASS, BUILDER_SIG),
+            JValue::from(zero_hash),
+        ),
+        (
+            "additionalHeaders",
+            format!("(Lcom/google/common/collect/ImmutableMap;){}", BUILDER_SIG),
+            JValue::from(headers),
+        ),
+    ];
+    for (name, sig, value) in setters.iter() {
+        builder = env
+            .call_method(builder, *name, sig.as_str(), &[*value])?
+            .l()?;
+    }
+    env.call_method(
+        builder,
+        "build",
+        "()Lcom/exonum/binding/core/blockchain/Block;",
+        &[],
+    )?
+    .l()
+}
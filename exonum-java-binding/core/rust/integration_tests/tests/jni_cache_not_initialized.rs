@@ -12,11 +12,67 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use java_bindings::utils::{assert_panics, jni_cache};
+use std::panic;
+
+use integration_tests::vm::create_vm_for_tests;
+use java_bindings::utils::{
+    assert_panics,
+    jni_cache::{self, CacheError},
+    unwrap_exc_or_default,
+};
 
 #[test]
 fn cache_not_initialized() {
+    assert!(!jni_cache::is_cache_initialized());
+    assert!(matches!(
+        jni_cache::runtime_adapter::try_execute_tx_id(),
+        Err(CacheError::NotInitialized)
+    ));
+    assert_panics("JNI cache is not initialized", || {
+        jni_cache::runtime_adapter::execute_tx_id()
+    });
+}
+
+#[test]
+fn init_cache_fails_without_classes() {
+    // Create a VM without EJB classes, which leaves the JNI cache uninitialized
+    let vm = create_vm_for_tests();
+    let env = vm.attach_current_thread().unwrap();
+
+    let err = jni_cache::try_init_cache(&env).unwrap_err();
+    assert!(
+        matches!(err, CacheError::MethodNotFound { .. }),
+        "Unexpected error: {}",
+        err
+    );
+    assert!(!env.exception_check().unwrap());
+    // The lookup fails after the methods of `java.lang` classes are resolved,
+    // but none of them is left cached.
+    assert!(jni_cache::is_cache_empty());
+    assert!(jni_cache::cached_entries().is_empty());
+    assert_eq!(jni_cache::cache_generation(), 0);
     assert_panics("JNI cache is not initialized", || {
         jni_cache::runtime_adapter::execute_tx_id()
     });
+
+    // The native calls made before the cache is initialized are reported explicitly
+    assert_panics("called before JNI_OnLoad", || {
+        jni_cache::ensure_loaded(&env)
+    });
+    // ... and thrown to Java by the exported functions without the cached classes
+    let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
+        Ok(())
+    });
+    unwrap_exc_or_default(&env, res);
+    let exception = env.exception_occurred().unwrap();
+    env.exception_clear().unwrap();
+    assert!(env
+        .is_instance_of(exception, "java/lang/RuntimeException")
+        .unwrap());
+
+    // The panicking version reports the missing method
+    assert_panics("not found", || jni_cache::init_cache(&env));
+    assert!(!jni_cache::is_cache_initialized());
+    assert!(jni_cache::is_cache_empty());
 }
@@ -0,0 +1,105 @@
+// Copyright 2018 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use integration_tests::vm::create_vm_for_tests_with_classes;
+use java_bindings::{
+    jni::{JNIEnv, JavaVM},
+    utils::{assert_panics, jni_cache},
+};
+
+use std::{
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+static CACHE_READY_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn count_cache_ready(_: &JNIEnv) {
+    assert!(jni_cache::is_cache_initialized());
+    CACHE_READY_CALLS.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn cache_released_on_unload_or_reset_and_reinitialized() {
+    // Create a VM, initializing the JNI cache
+    let vm = create_vm_for_tests_with_classes();
+    jni_cache::runtime_adapter::execute_tx_id();
+    jni_cache::classes_refs::java_lang_error();
+    assert_eq!(jni_cache::cache_generation(), 1);
+
+    let unloaded_vm = unsafe { JavaVM::from_raw(vm.get_java_vm_pointer()).unwrap() };
+    jni_cache::JNI_OnUnload(unloaded_vm, ptr::null_mut());
+    assert!(!jni_cache::is_cache_initialized());
+    assert_panics("JNI cache is not initialized", || {
+        jni_cache::runtime_adapter::execute_tx_id()
+    });
+
+    // The callbacks registered before the initialization are run once it completes.
+    jni_cache::on_cache_ready(count_cache_ready);
+    assert_eq!(CACHE_READY_CALLS.load(Ordering::SeqCst), 0);
+
+    let env = vm.attach_current_thread().unwrap();
+    jni_cache::init_cache(&env);
+    assert_eq!(CACHE_READY_CALLS.load(Ordering::SeqCst), 1);
+    assert!(jni_cache::is_cache_initialized());
+    assert!(!jni_cache::is_cache_empty());
+    assert_eq!(jni_cache::cache_generation(), 2);
+    jni_cache::runtime_adapter::execute_tx_id();
+    jni_cache::classes_refs::java_lang_error();
+
+    jni_cache::reset_cache();
+    assert!(!jni_cache::is_cache_initialized());
+    assert!(jni_cache::cached_entries().is_empty());
+    assert!(jni_cache::is_cache_empty());
+
+    jni_cache::init_cache(&env);
+    assert!(jni_cache::is_cache_initialized());
+    assert_eq!(jni_cache::cache_generation(), 3);
+    // The callbacks are run again on each initialization, and immediately
+    // if the cache is initialized.
+    assert_eq!(CACHE_READY_CALLS.load(Ordering::SeqCst), 2);
+    jni_cache::on_cache_ready(count_cache_ready);
+    assert_eq!(CACHE_READY_CALLS.load(Ordering::SeqCst), 3);
+    jni_cache::runtime_adapter::execute_tx_id();
+
+    // Reset waits for the adapter calls in progress
+    let call = jni_cache::adapter_call_guard();
+    let call_thread = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        drop(call);
+    });
+    jni_cache::reset_cache();
+    assert_eq!(jni_cache::adapter_calls_in_progress(), 0);
+    assert!(!jni_cache::is_cache_initialized());
+    call_thread.join().unwrap();
+
+    // The calls starting while the reset waits for the calls in progress are rejected
+    jni_cache::init_cache(&env);
+    let call = jni_cache::adapter_call_guard();
+    let reset_thread = thread::spawn(jni_cache::reset_cache);
+    while jni_cache::try_adapter_call_guard().is_ok() {
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert_panics("JNI cache is being reset", || {
+        jni_cache::adapter_call_guard();
+    });
+    assert_eq!(jni_cache::adapter_calls_in_progress(), 1);
+    assert!(jni_cache::is_cache_initialized());
+    drop(call);
+    reset_thread.join().unwrap();
+    assert!(!jni_cache::is_cache_initialized());
+    assert!(jni_cache::try_adapter_call_guard().is_ok());
+}
@@ -13,7 +13,9 @@ use java_bindings::{
         objects::{JObject, JThrowable},
         JNIEnv, JavaVM,
     },
-    jni_call_default, jni_call_transaction, Error, Executor, JniResult,
+    jni_call_default, jni_call_transaction,
+    utils::jni_cache::execution_exception,
+    Error, Executor, JniResult,
 };
 use lazy_static::lazy_static;
 
@@ -116,6 +118,24 @@ fn jni_call_transaction_execution_exception() {
     assert_eq!(err, expected);
 }
 
+#[test]
+fn jni_call_transaction_cached_execution_exception() {
+    let expected = ErrorMatch::from_fail(&FakeServiceError::Error)
+        .with_description_containing(EXPECTED_DESCRIPTION);
+    let err = jni_call_transaction(&EXECUTOR, |env| {
+        let exception = execution_exception::new_execution_exception(
+            env,
+            EXPECTED_CODE as i8,
+            EXPECTED_DESCRIPTION,
+        )?;
+        env.throw(exception)?;
+        trigger_jni_error(env)?;
+        Ok(())
+    })
+    .unwrap_err();
+    assert_eq!(err, expected);
+}
+
 #[test]
 fn jni_call_transaction_unexpected_execution_exception() {
     let err = jni_call_transaction(&EXECUTOR, |env| {
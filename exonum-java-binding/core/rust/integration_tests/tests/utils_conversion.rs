@@ -0,0 +1,90 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use integration_tests::vm::create_vm_for_tests;
+use java_bindings::{
+    jni::JavaVM,
+    utils::{convert_to_bytes, format_artifact_coordinate, format_artifact_id, ByteArrayError},
+    Executor,
+};
+use lazy_static::lazy_static;
+
+use std::{ptr, sync::Arc};
+
+lazy_static! {
+    static ref VM: Arc<JavaVM> = create_vm_for_tests();
+    pub static ref EXECUTOR: Executor = Executor::new(VM.clone());
+}
+
+#[test]
+fn convert_null_byte_array() {
+    EXECUTOR
+        .with_attached(|env| {
+            let result = convert_to_bytes(env, ptr::null_mut());
+            match result {
+                Err(ByteArrayError::NullByteArray) => {}
+                other => panic!("Unexpected result: {:?}", other),
+            }
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn convert_empty_byte_array() {
+    EXECUTOR
+        .with_attached(|env| {
+            let array = env.new_byte_array(0)?;
+            assert_eq!(convert_to_bytes(env, array).unwrap(), Vec::<u8>::new());
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn convert_byte_array_with_all_values() {
+    EXECUTOR
+        .with_attached(|env| {
+            let bytes: Vec<u8> = (0..=255).collect();
+            let array = env.byte_array_from_slice(&bytes)?;
+            // Java bytes are signed: the upper half of the values is negative.
+            let mut java_bytes = [0i8; 256];
+            env.get_byte_array_region(array, 0, &mut java_bytes)?;
+            assert_eq!(java_bytes[0x7f], 127);
+            assert_eq!(java_bytes[0x80], -128);
+            assert_eq!(java_bytes[0xff], -1);
+
+            assert_eq!(convert_to_bytes(env, array).unwrap(), bytes);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn format_artifact_id_as_hex() {
+    assert_eq!(format_artifact_id(&[]), "");
+    assert_eq!(format_artifact_id(&[0x00, 0x0a, 0xff]), "000aff");
+}
+
+#[test]
+fn format_artifact_coordinates() {
+    assert_eq!(
+        format_artifact_coordinate("com.acme/land-registry", "1.2.0"),
+        "com.acme:land-registry:1.2.0"
+    );
+    assert_eq!(
+        format_artifact_coordinate("timestamping", "1.0.0"),
+        "timestamping:1.0.0"
+    );
+}
@@ -16,15 +16,25 @@
 
 use integration_tests::vm::create_vm_for_tests_with_classes;
 use java_bindings::{
-    jni::{objects::JThrowable, JNIEnv, JavaVM},
+    jni::{
+        objects::{JObject, JThrowable, JValue},
+        JNIEnv, JavaVM,
+    },
     utils::{
-        check_error_on_exception, get_and_clear_java_exception, get_class_name,
-        get_exception_message, panic_on_exception,
+        any_to_string, assert_panics, capture_stack_trace, capture_stack_trace_with_limit,
+        check_error_on_exception, classify_exception, describe_object, describe_throwable,
+        describe_throwable_localized, describe_throwable_simple, describe_throwable_with_depth,
+        get_and_clear_java_exception, get_class_name, get_class_simple_name, get_exception_cause,
+        get_exception_message,
+        jni_cache::{classes_refs, execution_exception},
+        panic_on_exception, throw_for_kind, throw_illegal_state, throw_rust_panic,
+        with_local_frame, ExceptionKind, JavaErrorKind, DEFAULT_LOCAL_FRAME_CAPACITY,
+        DEFAULT_MAX_STACK_FRAMES,
     },
     Executor, JniErrorKind, JniResult,
 };
 use lazy_static::lazy_static;
-use std::sync::Arc;
+use std::{any::Any, sync::Arc};
 
 const ERROR_CLASS: &str = "java/lang/Error";
 const OOM_ERROR_CLASS: &str = "java/lang/OutOfMemoryError";
@@ -199,6 +209,424 @@ fn get_and_clear_java_exception_if_no_exception_occurred() {
         .unwrap();
 }
 
+#[test]
+fn describe_throwable_with_causes() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let cause = env.new_object(ARITHMETIC_EXCEPTION_CLASS, "()V", &[])?;
+            let exception = new_exception(env, CUSTOM_EXCEPTION_MESSAGE, cause)?;
+            assert_eq!(
+                describe_throwable(env, exception),
+                format!(
+                    "java.lang.Exception: {}\n  caused by: java.lang.ArithmeticException",
+                    CUSTOM_EXCEPTION_MESSAGE
+                )
+            );
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn describe_throwable_limits_depth() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let third = new_exception(env, "third", JObject::null())?;
+            let second = new_exception(env, "second", third.into())?;
+            let first = new_exception(env, "first", second.into())?;
+            assert_eq!(
+                describe_throwable_with_depth(env, first, 1),
+                "java.lang.Exception: first\n  \
+                 caused by: java.lang.Exception: second\n  \
+                 ..."
+            );
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn describe_throwable_with_simple_names() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let cause = env.new_object(ARITHMETIC_EXCEPTION_CLASS, "()V", &[])?;
+            let exception = new_exception(env, CUSTOM_EXCEPTION_MESSAGE, cause)?;
+            assert_eq!(
+                describe_throwable_simple(env, exception),
+                format!(
+                    "Exception: {}\n  caused by: ArithmeticException",
+                    CUSTOM_EXCEPTION_MESSAGE
+                )
+            );
+            assert_eq!(get_class_simple_name(env, cause)?, "ArithmeticException");
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn describe_throwable_localized_falls_back_to_message() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let cause = env.new_object(ARITHMETIC_EXCEPTION_CLASS, "()V", &[])?;
+            let exception = new_exception(env, CUSTOM_EXCEPTION_MESSAGE, cause)?;
+            // Throwable#getLocalizedMessage returns the message unless overridden
+            assert_eq!(
+                describe_throwable_localized(env, exception),
+                describe_throwable(env, exception)
+            );
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn throwable_message_and_cause() {
+    use java_bindings::utils::jni_cache::throwable;
+
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let cause = env.new_object(ARITHMETIC_EXCEPTION_CLASS, "()V", &[])?;
+            let exception = new_exception(env, CUSTOM_EXCEPTION_MESSAGE, cause)?;
+            assert_eq!(
+                throwable::message(env, exception)?,
+                Some(CUSTOM_EXCEPTION_MESSAGE.to_owned())
+            );
+            let exception_cause = throwable::cause(env, exception)?.expect("No cause");
+            assert!(env.is_same_object(exception_cause, cause)?);
+
+            // The cause has neither a message nor a cause.
+            let cause = JThrowable::from(cause);
+            assert_eq!(throwable::message(env, cause)?, None);
+            assert!(throwable::cause(env, cause)?.is_none());
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn capture_stack_trace_of_thrown_exception() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let exception = throw_number_format_exception(env);
+            let frames = capture_stack_trace(env, exception.into());
+            assert!(!frames.is_empty());
+            assert!(frames.len() <= DEFAULT_MAX_STACK_FRAMES);
+            assert!(
+                frames
+                    .iter()
+                    .any(|frame| frame.contains("java.lang.Integer.parseInt")),
+                "{:?}",
+                frames
+            );
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn with_local_frame_returns_result() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            // Far more local references than the table fits are created and released.
+            for i in 0..100_000 {
+                let length = with_local_frame(env, DEFAULT_LOCAL_FRAME_CAPACITY, || {
+                    let string = env.new_string(i.to_string())?;
+                    let string: String = env.get_string(string)?.into();
+                    Ok(string.len())
+                })?;
+                assert_eq!(length, i.to_string().len());
+            }
+
+            let result: JniResult<()> = with_local_frame(env, DEFAULT_LOCAL_FRAME_CAPACITY, || {
+                Err(JniErrorKind::NullPtr("test").into())
+            });
+            assert!(result.is_err());
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn with_local_frame_pops_frame_on_panic() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            for i in 0..100 {
+                assert_panics("Panic in frame", || {
+                    with_local_frame(env, DEFAULT_LOCAL_FRAME_CAPACITY, || -> JniResult<()> {
+                        env.new_string(i.to_string())?;
+                        panic!("Panic in frame")
+                    })
+                });
+            }
+            // The frames are popped, so the references of the current frame remain usable.
+            let string = env.new_string("outer")?;
+            let string: String = env.get_string(string)?.into();
+            assert_eq!(string, "outer");
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn capture_stack_trace_limits_frames() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let exception = throw_number_format_exception(env);
+            let frames = capture_stack_trace_with_limit(env, exception.into(), 1);
+            assert_eq!(frames.len(), 1);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn describe_object_uses_to_string() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let string = env.new_string(CUSTOM_EXCEPTION_MESSAGE)?;
+            assert_eq!(
+                describe_object(env, string.into()),
+                CUSTOM_EXCEPTION_MESSAGE
+            );
+            assert_eq!(describe_object(env, JObject::null()), "null");
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn describe_object_keeps_pending_exception() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let string = env.new_string(CUSTOM_EXCEPTION_MESSAGE)?;
+            assert!(throw(env, EXCEPTION_CLASS).is_err());
+            assert_eq!(describe_object(env, string.into()), "<failed to describe>");
+            assert!(env.exception_check()?);
+            env.exception_clear()?;
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn classify_exceptions() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let cases = [
+                (
+                    "java/lang/IllegalArgumentException",
+                    ExceptionKind::IllegalArgument,
+                ),
+                (
+                    "java/lang/IllegalStateException",
+                    ExceptionKind::IllegalState,
+                ),
+                (
+                    "java/util/NoSuchElementException",
+                    ExceptionKind::NoSuchElement,
+                ),
+                (
+                    "java/lang/IndexOutOfBoundsException",
+                    ExceptionKind::IndexOutOfBounds,
+                ),
+                // A subclass of IndexOutOfBoundsException
+                (
+                    "java/lang/ArrayIndexOutOfBoundsException",
+                    ExceptionKind::IndexOutOfBounds,
+                ),
+                (ARITHMETIC_EXCEPTION_CLASS, ExceptionKind::Runtime),
+                (OOM_ERROR_CLASS, ExceptionKind::Error),
+                (EXCEPTION_CLASS, ExceptionKind::Other),
+            ];
+            for (class, expected_kind) in &cases {
+                let exception = env.new_object(*class, "()V", &[])?;
+                assert_eq!(
+                    classify_exception(env, exception.into()),
+                    *expected_kind,
+                    "{}",
+                    class
+                );
+            }
+
+            let execution_exception =
+                execution_exception::new_execution_exception(env, 1, CUSTOM_EXCEPTION_MESSAGE)?;
+            assert_eq!(
+                classify_exception(env, execution_exception),
+                ExceptionKind::Execution
+            );
+
+            let cause = env.new_object(ARITHMETIC_EXCEPTION_CLASS, "()V", &[])?;
+            let unexpected_exception = env.new_object(
+                &classes_refs::unexpected_execution_exception(),
+                "(Ljava/lang/Throwable;)V",
+                &[JValue::from(cause)],
+            )?;
+            assert_eq!(
+                classify_exception(env, unexpected_exception.into()),
+                ExceptionKind::UnexpectedExecution
+            );
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn throw_illegal_state_exception() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            throw_illegal_state(env, CUSTOM_EXCEPTION_MESSAGE)?;
+            let exception = get_and_clear_java_exception(env);
+            assert_eq!(
+                get_class_name(env, exception)?,
+                "java.lang.IllegalStateException"
+            );
+            assert_eq!(
+                get_exception_message(env, exception)?,
+                Some(CUSTOM_EXCEPTION_MESSAGE.to_owned())
+            );
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn throw_runtime_exception_with_location() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let line = line!() + 1;
+            java_bindings::throw_runtime_exception!(env, "Failed with code {}", 42)?;
+            let exception = get_and_clear_java_exception(env);
+            assert_eq!(
+                get_class_name(env, exception)?,
+                "java.lang.RuntimeException"
+            );
+            let message = get_exception_message(env, exception)?.unwrap();
+            assert_eq!(
+                message,
+                format!("[{}:{}] Failed with code 42", file!(), line)
+            );
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn throw_for_each_kind() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let kinds = [
+                (JavaErrorKind::Error, "java.lang.Error"),
+                (JavaErrorKind::Runtime, "java.lang.RuntimeException"),
+                (
+                    JavaErrorKind::IllegalArgument,
+                    "java.lang.IllegalArgumentException",
+                ),
+                (
+                    JavaErrorKind::IllegalState,
+                    "java.lang.IllegalStateException",
+                ),
+                (
+                    JavaErrorKind::UnexpectedExecution,
+                    "com.exonum.binding.core.runtime.UnexpectedExecutionException",
+                ),
+            ];
+            for (kind, class_name) in kinds.iter() {
+                throw_for_kind(env, *kind, CUSTOM_EXCEPTION_MESSAGE, None)?;
+                let exception = get_and_clear_java_exception(env);
+                assert_eq!(get_class_name(env, exception)?, *class_name);
+            }
+
+            throw_for_kind(
+                env,
+                JavaErrorKind::Execution,
+                CUSTOM_EXCEPTION_MESSAGE,
+                Some(7),
+            )?;
+            let exception = get_and_clear_java_exception(env);
+            assert_eq!(execution_exception::read_error_code(env, exception)?, 7);
+            assert_eq!(
+                get_exception_message(env, exception)?,
+                Some(CUSTOM_EXCEPTION_MESSAGE.to_owned())
+            );
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn throw_rust_panic_as_unexpected_execution_exception() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let payloads: Vec<Box<dyn Any + Send>> = vec![
+                Box::new("Static message"),
+                Box::new("Owned message".to_owned()),
+            ];
+            for payload in payloads {
+                let expected = format!("Rust panic: {}", any_to_string(&payload));
+                // The panic replaces a pending exception.
+                throw(env, EXCEPTION_CLASS)?;
+                throw_rust_panic(env, payload);
+                let exception = get_and_clear_java_exception(env);
+                assert_eq!(
+                    get_class_name(env, exception)?,
+                    "com.exonum.binding.core.runtime.UnexpectedExecutionException"
+                );
+                let cause = get_exception_cause(env, exception)?;
+                assert_eq!(get_exception_message(env, cause)?, Some(expected));
+            }
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn throw_execution_exception_requires_code() {
+    EXECUTOR
+        .with_attached(|env: &JNIEnv| {
+            let result = throw_for_kind(
+                env,
+                JavaErrorKind::Execution,
+                CUSTOM_EXCEPTION_MESSAGE,
+                None,
+            );
+            assert!(result.is_err());
+            assert!(!env.exception_check()?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+/// Throws `NumberFormatException` from the Java code, so that it has a stack trace,
+/// and returns it.
+fn throw_number_format_exception<'a>(env: &'a JNIEnv) -> JObject<'a> {
+    let result = env.call_static_method(
+        "java/lang/Integer",
+        "parseInt",
+        "(Ljava/lang/String;)I",
+        &[JValue::from(JObject::from(env.new_string("NaN").unwrap()))],
+    );
+    assert!(result.is_err());
+    get_and_clear_java_exception(env)
+}
+
+fn new_exception<'a>(
+    env: &JNIEnv<'a>,
+    message: &str,
+    cause: JObject<'a>,
+) -> JniResult<JThrowable<'a>> {
+    let message = env.new_string(message)?;
+    env.new_object(
+        EXCEPTION_CLASS,
+        "(Ljava/lang/String;Ljava/lang/Throwable;)V",
+        &[JObject::from(message).into(), cause.into()],
+    )
+    .map(JThrowable::from)
+}
+
 fn throw(env: &JNIEnv, exception_class: &str) -> JniResult<()> {
     let ex: JThrowable = env.new_object(exception_class, "()V", &[])?.into();
     env.throw(ex)?;
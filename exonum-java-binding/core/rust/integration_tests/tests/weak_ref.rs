@@ -0,0 +1,82 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use integration_tests::vm::create_vm_for_tests;
+use java_bindings::{
+    jni::{objects::JObject, JavaVM},
+    utils::weak_ref::{new_weak_ref, upgrade_weak},
+    Executor,
+};
+use lazy_static::lazy_static;
+
+use std::{sync::Arc, thread, time::Duration};
+
+lazy_static! {
+    static ref VM: Arc<JavaVM> = create_vm_for_tests();
+    pub static ref EXECUTOR: Executor = Executor::new(VM.clone());
+}
+
+const GC_ATTEMPTS: usize = 10;
+
+#[test]
+fn upgrade_weak_ref_of_live_object() {
+    EXECUTOR
+        .with_attached(|env| {
+            let obj = env.new_object("java/lang/Object", "()V", &[])?;
+            let _strong = env.new_global_ref(obj)?;
+            let weak = new_weak_ref(env, obj)?;
+
+            env.call_static_method("java/lang/System", "gc", "()V", &[])?;
+            let upgraded = upgrade_weak(env, &weak)?.expect("Object is collected");
+            assert!(env.is_same_object(upgraded.as_obj(), obj)?);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn weak_ref_is_cleared_after_gc() {
+    // The local reference to the object is released with the local frame of the closure.
+    let weak = EXECUTOR
+        .with_attached(|env| {
+            let obj = env.new_object("java/lang/Object", "()V", &[])?;
+            new_weak_ref(env, obj)
+        })
+        .unwrap();
+
+    let mut cleared = false;
+    for _ in 0..GC_ATTEMPTS {
+        cleared = EXECUTOR
+            .with_attached(|env| {
+                env.call_static_method("java/lang/System", "gc", "()V", &[])?;
+                Ok(upgrade_weak(env, &weak)?.is_none())
+            })
+            .unwrap();
+        if cleared {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert!(cleared, "The object is not collected");
+}
+
+#[test]
+fn weak_ref_of_null_is_error() {
+    EXECUTOR
+        .with_attached(|env| {
+            assert!(new_weak_ref(env, JObject::null()).is_err());
+            Ok(())
+        })
+        .unwrap();
+}
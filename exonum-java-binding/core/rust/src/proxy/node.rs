@@ -32,7 +32,7 @@ use std::{panic, ptr};
 use crate::{
     handle::{cast_handle, drop_handle, to_handle, Handle},
     storage::into_erased_access,
-    utils::{unwrap_exc_or, unwrap_exc_or_default, unwrap_jni_verbose},
+    utils::{jni_cache, unwrap_exc_or, unwrap_exc_or_default, unwrap_jni_verbose},
     JniResult,
 };
 
@@ -94,6 +94,7 @@ pub extern "system" fn Java_com_exonum_binding_core_runtime_NodeProxy_nativeSubm
 ) -> jbyteArray {
     use crate::utils::convert_hash;
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let node = cast_handle::<Node>(node_handle);
         let hash = unwrap_jni_verbose(
             &env,
@@ -134,6 +135,7 @@ pub extern "system" fn Java_com_exonum_binding_core_runtime_NodeProxy_nativeCrea
     node_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let node = cast_handle::<Node>(node_handle);
         let snapshot = node.create_snapshot();
         let access = unsafe { into_erased_access(snapshot) };
@@ -150,6 +152,7 @@ pub extern "system" fn Java_com_exonum_binding_core_runtime_NodeProxy_nativeGetP
     node_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let node = cast_handle::<Node>(node_handle);
         let public_key = node.public_key();
         Ok(unwrap_jni_verbose(
@@ -22,8 +22,8 @@ use exonum::{
         migrations::{InitMigrationError, MigrationScript},
         oneshot,
         versioning::Version,
-        ArtifactId, Caller, ExecutionContext, ExecutionError, InstanceId, InstanceSpec, Mailbox,
-        Runtime, RuntimeIdentifier, SnapshotExt, WellKnownRuntime,
+        ArtifactId, Caller, ExecutionContext, ExecutionError, InstanceId, InstanceSpec,
+        InstanceStatus, Mailbox, Runtime, RuntimeIdentifier, SnapshotExt, WellKnownRuntime,
     },
 };
 use jni::{
@@ -32,6 +32,7 @@ use jni::{
     sys::jint,
     Executor,
 };
+use log::trace;
 
 use std::fmt;
 
@@ -39,8 +40,15 @@ use crate::{
     runtime::{jni_call_default, jni_call_transaction, Error},
     storage::{blockchain_data_from_execution_context, into_erased_access},
     to_handle,
-    utils::{jni_cache::runtime_adapter, panic_on_exception, proto_to_java_bytes, unwrap_jni},
-    Node,
+    utils::{
+        format_artifact_coordinate, format_artifact_id,
+        jni_cache::{
+            self,
+            runtime_adapter::{self, ExecuteTxArgs},
+        },
+        panic_on_exception, proto_to_java_bytes, unwrap_jni,
+    },
+    JniError, Node,
 };
 use exonum::runtime::InstanceState;
 
@@ -75,12 +83,22 @@ impl JavaRuntimeProxy {
             .find_validator(|validator_keys| *pub_key == validator_keys.service_key)
             .map_or(DEFAULT_VALIDATOR_ID, |id| i32::from(id.0))
     }
+
+    /// Formats the artifact for the traces as `artifact=<coordinate> artifact_id=<hex>`.
+    fn format_artifact(artifact_id: &ArtifactId) -> String {
+        format!(
+            "artifact={} artifact_id={}",
+            format_artifact_coordinate(&artifact_id.name, &artifact_id.version.to_string()),
+            format_artifact_id(&artifact_id.to_bytes())
+        )
+    }
 }
 
 impl Runtime for JavaRuntimeProxy {
     fn initialize(&mut self, blockchain: &Blockchain) {
         self.blockchain = Some(blockchain.clone());
 
+        let _call = jni_cache::adapter_call_guard();
         unwrap_jni(self.exec.with_attached(|env| {
             let node_handle = to_handle(Node::new(blockchain.clone()));
 
@@ -99,6 +117,7 @@ impl Runtime for JavaRuntimeProxy {
         artifact_id: ArtifactId,
         deploy_spec: Vec<u8>,
     ) -> oneshot::Receiver {
+        trace!("deployArtifact: {}", Self::format_artifact(&artifact_id));
         let result = jni_call_default(&self.exec, |env| {
             let artifact_id = JObject::from(proto_to_java_bytes(env, &artifact_id)?);
             let spec = JObject::from(env.byte_array_from_slice(&deploy_spec)?);
@@ -119,20 +138,24 @@ impl Runtime for JavaRuntimeProxy {
     }
 
     fn is_artifact_deployed(&self, artifact_id: &ArtifactId) -> bool {
-        unwrap_jni(self.exec.with_attached(|env| {
-            let artifact_id = JObject::from(proto_to_java_bytes(env, artifact_id)?);
-
-            panic_on_exception(
+        let _call = jni_cache::adapter_call_guard();
+        let artifact_id_bytes = artifact_id.to_bytes();
+        let deployed = unwrap_jni(self.exec.with_attached(|env| {
+            Ok(panic_on_exception(
                 env,
-                env.call_method_unchecked(
+                runtime_adapter::is_artifact_deployed(
+                    env,
                     self.runtime_adapter.as_obj(),
-                    runtime_adapter::is_artifact_deployed_id(),
-                    JavaType::Primitive(Primitive::Boolean),
-                    &[JValue::from(artifact_id)],
+                    &artifact_id_bytes,
                 ),
-            )
-            .z()
-        }))
+            ))
+        }));
+        trace!(
+            "isArtifactDeployed: {} -> {}",
+            Self::format_artifact(artifact_id),
+            deployed
+        );
+        deployed
     }
 
     fn initiate_adding_service(
@@ -202,6 +225,7 @@ impl Runtime for JavaRuntimeProxy {
     }
 
     fn update_service_status(&mut self, _snapshot: &dyn Snapshot, state: &InstanceState) {
+        let _call = jni_cache::adapter_call_guard();
         unwrap_jni(self.exec.with_attached(|env| {
             let instance_spec = JObject::from(proto_to_java_bytes(env, &state.spec)?);
             let instance_status =
@@ -218,6 +242,9 @@ impl Runtime for JavaRuntimeProxy {
             );
             Ok(())
         }));
+        if let Some(InstanceStatus::Stopped) = state.status {
+            jni_cache::service_classes::release_service_classes(state.spec.id);
+        }
     }
 
     fn migrate(
@@ -251,34 +278,20 @@ impl Runtime for JavaRuntimeProxy {
         };
 
         jni_call_transaction(&self.exec, |env| {
-            let service_id = context.instance().id as i32;
-            let interface_name = JObject::from(env.new_string(context.interface_name())?);
-            let tx_id = method_id as i32;
-            let args = JObject::from(env.byte_array_from_slice(arguments)?);
-            let blockchain_data_handle =
-                unsafe { blockchain_data_from_execution_context(&context) };
-            let caller_id = tx_info.0;
             let message_hash = tx_info.1.to_bytes();
-            let message_hash = JObject::from(env.byte_array_from_slice(&message_hash)?);
             let author_pk = tx_info.2.to_bytes();
-            let author_pk = JObject::from(env.byte_array_from_slice(&author_pk)?);
-
-            env.call_method_unchecked(
-                self.runtime_adapter.as_obj(),
-                runtime_adapter::execute_tx_id(),
-                JavaType::Primitive(Primitive::Void),
-                &[
-                    JValue::from(service_id),
-                    JValue::from(interface_name),
-                    JValue::from(tx_id),
-                    JValue::from(args),
-                    JValue::from(blockchain_data_handle),
-                    JValue::from(caller_id as jint),
-                    JValue::from(message_hash),
-                    JValue::from(author_pk),
-                ],
-            )
-            .and_then(JValue::v)
+            let args = ExecuteTxArgs {
+                service_id: context.instance().id as i32,
+                interface_name: context.interface_name(),
+                tx_id: method_id as i32,
+                arguments,
+                blockchain_data_handle: unsafe { blockchain_data_from_execution_context(&context) },
+                caller_id: tx_info.0 as jint,
+                message_hash: &message_hash,
+                author_pk: &author_pk,
+            };
+            runtime_adapter::execute_transaction(env, self.runtime_adapter.as_obj(), args)
+                .map_err(JniError::from)
         })
     }
 
@@ -319,6 +332,7 @@ impl Runtime for JavaRuntimeProxy {
     }
 
     fn after_commit(&mut self, snapshot: &dyn Snapshot, _mailbox: &mut Mailbox) {
+        let _call = jni_cache::adapter_call_guard();
         unwrap_jni(self.exec.with_attached(|env| {
             let access_handle = unsafe { to_handle(into_erased_access(snapshot)) };
             let public_key = self
@@ -350,6 +364,7 @@ impl Runtime for JavaRuntimeProxy {
 
 impl Drop for JavaRuntimeProxy {
     fn drop(&mut self) {
+        let _call = jni_cache::adapter_call_guard();
         unwrap_jni(self.exec.with_attached(|env| {
             panic_on_exception(
                 env,
@@ -16,7 +16,6 @@ use exonum::runtime::{ErrorKind, ExecutionError, ExecutionFail};
 use exonum_derive::ExecutionFail;
 use jni::{
     objects::{GlobalRef, JObject},
-    signature::{JavaType, Primitive},
     Executor, JNIEnv,
 };
 
@@ -24,7 +23,7 @@ use crate::{
     utils::{
         describe_java_exception, get_and_clear_java_exception, get_exception_cause,
         get_exception_message,
-        jni_cache::{classes_refs, execution_exception},
+        jni_cache::{self, classes_refs, execution_exception},
         unwrap_jni,
     },
     JniError, JniErrorKind, JniResult,
@@ -57,9 +56,8 @@ mod exception_handlers {
     };
 
     pub const TX_EXECUTION: &ExceptionHandler = &|env, exception| {
-        let code = unwrap_jni(get_tx_error_code(env, exception)) as u8;
-        let message = unwrap_jni(get_exception_message(env, exception)).unwrap_or_default();
-        ExecutionError::service(code, message)
+        let error = unwrap_jni(execution_exception::to_execution_error(env, exception));
+        ExecutionError::service(error.code as u8, error.description)
     };
 
     pub const TX_UNEXPECTED: &ExceptionHandler = &|env, exception| {
@@ -161,6 +159,7 @@ where
     F: FnOnce(&JNIEnv) -> JniResult<R>,
     H: Fn(&JNIEnv, JObject) -> ExecutionError,
 {
+    let _call = jni_cache::adapter_call_guard();
     let mut execution_error: Option<ExecutionError> = None;
 
     // Any errors or exceptions from `f` closure (managed native or java code)
@@ -224,15 +223,3 @@ where
         _ => Error::JniError.with_description(err.to_string()),
     }
 }
-
-/// Returns the error code of the `ExecutionException`.
-fn get_tx_error_code(env: &JNIEnv, exception: JObject) -> JniResult<i8> {
-    assert!(!exception.is_null(), "Exception is null");
-    let err_code = env.call_method_unchecked(
-        exception,
-        execution_exception::get_error_code_id(),
-        JavaType::Primitive(Primitive::Byte),
-        &[],
-    )?;
-    err_code.b()
-}
@@ -12,7 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use jni::{objects::JClass, sys::jstring, JNIEnv};
+use jni::{
+    objects::{JClass, JObject},
+    sys::jstring,
+    JNIEnv,
+};
+
+use std::{error::Error, fmt};
+
+use crate::{
+    utils::{convert_to_string, jni_cache},
+    JniResult,
+};
+
+/// The version of the Java library (the JAR) this native library is compatible with.
+pub const EXPECTED_JAR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const LIBRARY_LOADER_CLASS: &str = "com/exonum/binding/core/util/LibraryLoader";
+const JAR_VERSION_FIELD: &str = "JAVA_BINDING_VERSION";
 
 /// Returns the current version of the library.
 #[no_mangle]
@@ -28,6 +45,69 @@ pub fn get_lib_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
+/// The version of the Java library does not match `EXPECTED_JAR_VERSION`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VersionMismatch {
+    /// The version of the Java library the native library expects.
+    pub expected: String,
+    /// The version reported by the Java library, or `None` if it cannot be read
+    /// (e.g., the JAR is too old to report it).
+    pub actual: Option<String>,
+}
+
+impl fmt::Display for VersionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Native library expects Java library version {}, but found {}; \
+             check that both the native library and the JAR are updated",
+            self.expected,
+            self.actual.as_deref().unwrap_or("an unknown version")
+        )
+    }
+}
+
+impl Error for VersionMismatch {}
+
+/// Checks that the Java library the given `ServiceRuntimeAdapter` is loaded from has
+/// the version this native library expects (`EXPECTED_JAR_VERSION`), reading
+/// `LibraryLoader.JAVA_BINDING_VERSION` with the class loader of the adapter.
+///
+/// Catches the native library and the JAR updated separately with a clear message,
+/// instead of a missing method error on the first call. Any exception occurred
+/// while reading the version is cleared.
+pub fn verify_version_compatibility(env: &JNIEnv, adapter: JObject) -> Result<(), VersionMismatch> {
+    let actual = read_jar_version(env, adapter).ok();
+    if actual.is_none() {
+        let _ = env.exception_clear();
+    }
+    if actual.as_deref() == Some(EXPECTED_JAR_VERSION) {
+        Ok(())
+    } else {
+        Err(VersionMismatch {
+            expected: EXPECTED_JAR_VERSION.to_owned(),
+            actual,
+        })
+    }
+}
+
+fn read_jar_version(env: &JNIEnv, adapter: JObject) -> JniResult<String> {
+    let adapter_class = env.get_object_class(adapter)?;
+    let loader = env
+        .call_method(
+            adapter_class,
+            "getClassLoader",
+            "()Ljava/lang/ClassLoader;",
+            &[],
+        )?
+        .l()?;
+    let loader_class = jni_cache::get_class_with_loader(env, LIBRARY_LOADER_CLASS, loader)?;
+    let version = env
+        .get_static_field(&*loader_class, JAR_VERSION_FIELD, "Ljava/lang/String;")?
+        .l()?;
+    convert_to_string(env, version)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -38,4 +118,21 @@ mod tests {
         let version = get_lib_version();
         assert!(!version.is_empty());
     }
+
+    #[test]
+    fn version_mismatch_includes_versions() {
+        let mismatch = VersionMismatch {
+            expected: "1.0.0".to_owned(),
+            actual: Some("0.9.0".to_owned()),
+        };
+        let message = mismatch.to_string();
+        assert!(message.contains("1.0.0"), "{}", message);
+        assert!(message.contains("0.9.0"), "{}", message);
+
+        let unknown = VersionMismatch {
+            expected: "1.0.0".to_owned(),
+            actual: None,
+        };
+        assert!(unknown.to_string().contains("unknown version"));
+    }
 }
@@ -23,5 +23,7 @@ mod paths;
 pub use self::config::*;
 pub use self::error::*;
 pub use self::java_runtime_factory::*;
-pub use self::library_loader::get_lib_version;
+pub use self::library_loader::{
+    get_lib_version, verify_version_compatibility, VersionMismatch, EXPECTED_JAR_VERSION,
+};
 pub use self::paths::*;
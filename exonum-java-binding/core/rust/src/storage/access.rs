@@ -32,7 +32,7 @@ use std::{num::NonZeroU64, panic, rc::Rc};
 
 use crate::{
     handle::{self, Handle},
-    utils,
+    utils::{self, jni_cache},
 };
 
 /// Prolongs lifetime of the GenericRawAccess.
@@ -185,6 +185,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_AbstractAcc
     access_handle: Handle,
 ) -> jboolean {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         let can_modify = access.is_mutable();
         Ok(can_modify as jboolean)
@@ -205,6 +206,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_AbstractAcc
     id_in_group: jbyteArray,
 ) -> jlong {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         let address = utils::convert_to_index_address(&env, name, id_in_group)?;
         match access.find_index_id(address).unwrap() {
@@ -10,7 +10,7 @@ use std::{panic, ptr};
 use crate::{
     handle,
     storage::EjbAccessExt,
-    utils::{self, convert_to_string, proto_to_java_bytes},
+    utils::{self, convert_to_string, jni_cache, proto_to_java_bytes},
 };
 
 /// Returns IndexProof (serialized to protobuf) for specified index.
@@ -28,6 +28,7 @@ pub extern "system" fn Java_com_exonum_binding_core_blockchain_BlockchainProofs_
     full_index_name: jstring,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let name = convert_to_string(&env, full_index_name)?;
         let access = handle::cast_handle::<ErasedAccess>(snapshot_handle);
         let proof = access.proof_for_index(&name);
@@ -52,6 +53,7 @@ pub extern "system" fn Java_com_exonum_binding_core_blockchain_BlockchainProofs_
     block_height: jlong,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let access = handle::cast_handle::<ErasedAccess>(snapshot_handle);
         let proof = access.proof_for_block(block_height as u64);
         match proof {
@@ -10,7 +10,7 @@ use std::panic;
 
 use crate::{
     handle::{self, Handle},
-    utils,
+    utils::{self, jni_cache},
 };
 
 type BlockchainData = exonum::runtime::BlockchainData<GenericRawAccess<'static>>;
@@ -39,6 +39,7 @@ pub extern "system" fn Java_com_exonum_binding_core_blockchain_BlockchainData_na
     instance_name: jstring,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let access = handle::cast_handle::<ErasedAccess<'static>>(base_access_handle);
         let instance_name = utils::convert_to_string(&env, instance_name)?;
         let blockchain_data = match access {
@@ -72,6 +73,7 @@ pub extern "system" fn Java_com_exonum_binding_core_blockchain_BlockchainData_na
     handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let blockchain_data = handle::cast_handle::<BlockchainData>(handle);
         let prefixed_access = blockchain_data.for_executing_service();
         let service_access = ErasedAccess::from(prefixed_access);
@@ -89,6 +91,7 @@ pub extern "system" fn Java_com_exonum_binding_core_blockchain_BlockchainData_na
     handle: Handle,
 ) -> jstring {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let blockchain_data = handle::cast_handle::<BlockchainData>(handle);
         let service_name = blockchain_data.instance_name();
         let service_name = JObject::from(env.new_string(service_name)?);
@@ -109,6 +112,7 @@ pub extern "system" fn Java_com_exonum_binding_core_blockchain_BlockchainData_na
     service_name: jstring,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let blockchain_data = handle::cast_handle::<BlockchainData>(handle);
         let service_name = utils::convert_to_string(&env, service_name)?;
         let service_data = blockchain_data
@@ -132,6 +136,7 @@ pub extern "system" fn Java_com_exonum_binding_core_blockchain_BlockchainData_na
     handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let blockchain_data = handle::cast_handle::<BlockchainData>(handle);
         let unstructured_access = blockchain_data.unstructured_access();
         let unstructured_access = ErasedAccess::from(unstructured_access);
@@ -28,7 +28,7 @@ use std::{panic, ptr};
 use crate::{
     handle::{self, Handle},
     storage::Value,
-    utils,
+    utils::{self, jni_cache},
 };
 
 type Index = Entry<GenericRawAccess<'static>, Value>;
@@ -42,6 +42,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_EntryIndexPr
     access_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let address = utils::convert_to_string(&env, address)?;
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         let index: Index = access.get_entry(address);
@@ -68,6 +69,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_EntryIndexPr
     entry_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let entry = handle::cast_handle::<Index>(entry_handle);
         let value = entry.get();
         utils::optional_array_to_java(&env, value)
@@ -99,6 +101,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_EntryIndexPr
     value: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let entry = handle::cast_handle::<Index>(entry_handle);
         let value = env.convert_byte_array(value)?;
         entry.set(value);
@@ -20,7 +20,7 @@ use std::panic;
 use crate::{
     handle::{self, acquire_handle_ownership, to_handle, Handle},
     storage::EjbAccessExt,
-    utils,
+    utils::{self, jni_cache},
 };
 
 /// Creates checkpoint for `Fork`.
@@ -35,6 +35,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_Fork_native
     access_handle: Handle,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         access.create_checkpoint();
         Ok(())
@@ -54,6 +55,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_Fork_native
     access_handle: Handle,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         access.rollback();
         Ok(())
@@ -69,6 +71,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_Fork_native
     access_handle: Handle,
 ) -> jboolean {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         Ok(access.can_rollback() as jboolean)
     });
@@ -83,6 +86,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_Fork_native
     access_handle: Handle,
 ) -> jboolean {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         Ok(access.can_convert_into_fork() as jboolean)
     });
@@ -98,6 +102,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_Fork_native
     access_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let access: Box<ErasedAccess> = acquire_handle_ownership(access_handle);
         let fork = access.into_fork();
         let patch = fork.into_patch();
@@ -29,7 +29,7 @@ use jni::{
 use crate::{
     handle::{self, Handle},
     storage::Key,
-    utils,
+    utils::{self, jni_cache},
 };
 
 type Index = KeySetIndex<GenericRawAccess<'static>, Key>;
@@ -46,6 +46,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_KeySetIndexP
     access_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let address = utils::convert_to_index_address(&env, name, id_in_group)?;
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         let index: Index = access.get_key_set(address);
@@ -73,6 +74,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_KeySetIndexP
     value: jbyteArray,
 ) -> jboolean {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let set = handle::cast_handle::<Index>(set_handle);
         let value = env.convert_byte_array(value)?;
         let contains = set.contains(&value);
@@ -89,6 +91,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_KeySetIndexP
     set_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let set = handle::cast_handle::<Index>(set_handle);
         let iter = set.iter();
         Ok(handle::to_handle(iter))
@@ -105,6 +108,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_KeySetIndexP
     from: jbyteArray,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let set = handle::cast_handle::<Index>(set_handle);
         let from = env.convert_byte_array(from)?;
         let iter = set.iter_from(&from);
@@ -122,6 +126,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_KeySetIndexP
     value: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let set = handle::cast_handle::<Index>(set_handle);
         let value = env.convert_byte_array(value)?;
         set.insert(&value);
@@ -139,6 +144,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_KeySetIndexP
     value: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let set = handle::cast_handle::<Index>(set_handle);
         let value = env.convert_byte_array(value)?;
         set.remove(&value);
@@ -155,6 +161,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_KeySetIndexP
     set_handle: Handle,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let set = handle::cast_handle::<Index>(set_handle);
         set.clear();
         Ok(())
@@ -170,6 +177,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_KeySetIndexP
     iter_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iter = handle::cast_handle::<KeyIter>(iter_handle);
         utils::optional_array_to_java(&env, iter.next())
     });
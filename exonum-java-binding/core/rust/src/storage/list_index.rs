@@ -29,7 +29,7 @@ use jni::{
 use crate::{
     handle::{self, Handle},
     storage::Value,
-    utils,
+    utils::{self, jni_cache},
 };
 
 type Index = ListIndex<GenericRawAccess<'static>, Value>;
@@ -46,6 +46,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     access_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let address = utils::convert_to_index_address(&env, name, id_in_group)?;
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         let index: Index = access.get_list(address);
@@ -73,6 +74,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     index: jlong,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let value = list.get(index as u64);
         utils::optional_array_to_java(&env, value)
@@ -88,6 +90,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     list_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let value = list.last();
         utils::optional_array_to_java(&env, value)
@@ -103,6 +106,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     list_handle: Handle,
 ) -> jboolean {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let is_empty = list.is_empty();
         Ok(is_empty as jboolean)
@@ -118,6 +122,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     list_handle: Handle,
 ) -> jlong {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let len = list.len();
         Ok(len as jlong)
@@ -133,6 +138,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     list_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let iter = list.iter();
         Ok(handle::to_handle(iter))
@@ -149,6 +155,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     index_from: jlong,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let iter = list.iter_from(index_from as u64);
         Ok(handle::to_handle(iter))
@@ -165,6 +172,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     value: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let value = env.convert_byte_array(value)?;
         list.push(value);
@@ -181,6 +189,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     list_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let value = list.pop();
         utils::optional_array_to_java(&env, value)
@@ -197,6 +206,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     len: jlong,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         list.truncate(len as u64);
         Ok(())
@@ -214,6 +224,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     value: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let value = env.convert_byte_array(value)?;
         list.set(index as u64, value);
@@ -230,6 +241,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     list_handle: Handle,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         list.clear();
         Ok(())
@@ -245,6 +257,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ListIndexPro
     iter_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iter = handle::cast_handle::<Iter>(iter_handle);
         utils::optional_array_to_java(&env, iter.next())
     });
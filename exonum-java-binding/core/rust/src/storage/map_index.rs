@@ -29,7 +29,7 @@ use jni::{
 use crate::{
     handle::{self, Handle},
     storage::{Key, PairIter, Value},
-    utils,
+    utils::{self, jni_cache},
 };
 
 type Index = MapIndex<GenericRawAccess<'static>, Key, Value>;
@@ -50,6 +50,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     access_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let address = utils::convert_to_index_address(&env, name, id_in_group)?;
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         let index: Index = access.get_map(address);
@@ -77,6 +78,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     key: jbyteArray,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let value = map.get(&key);
@@ -94,6 +96,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     key: jbyteArray,
 ) -> jboolean {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let contains = map.contains(&key);
@@ -110,6 +113,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     map_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let iter = map.iter();
         let iter = Iter::new(&env, iter, JAVA_ENTRY_FQN)?;
@@ -126,6 +130,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     map_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let keys = map.keys();
         Ok(handle::to_handle(keys))
@@ -141,6 +146,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     map_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let values = map.values();
         Ok(handle::to_handle(values))
@@ -157,6 +163,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     key: jbyteArray,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let iter = map.iter_from(&key);
@@ -175,6 +182,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     key: jbyteArray,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let keys = map.keys_from(&key);
@@ -192,6 +200,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     key: jbyteArray,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let values = map.values_from(&key);
@@ -210,6 +219,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     value: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let value = env.convert_byte_array(value)?;
@@ -228,6 +238,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     key: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         map.remove(&key);
@@ -244,6 +255,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     map_handle: Handle,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         map.clear();
         Ok(())
@@ -259,6 +271,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     iter_handle: Handle,
 ) -> jobject {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iterWrapper = handle::cast_handle::<Iter>(iter_handle);
         match iterWrapper.iter.next() {
             Some(val) => {
@@ -266,7 +279,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
                 let value = env.byte_array_from_slice(&val.1)?;
                 Ok(env
                     .new_object_unchecked(
-                        &iterWrapper.element_class,
+                        &*iterWrapper.element_class,
                         iterWrapper.constructor_id,
                         &[key.into(), value.into()],
                     )?
@@ -296,6 +309,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     iter_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iter = handle::cast_handle::<KeysIter>(iter_handle);
         utils::optional_array_to_java(&env, iter.next())
     });
@@ -320,6 +334,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_MapIndexProx
     iter_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iter = handle::cast_handle::<ValuesIter>(iter_handle);
         utils::optional_array_to_java(&env, iter.next())
     });
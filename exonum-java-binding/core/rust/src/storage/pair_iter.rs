@@ -12,12 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use jni::objects::{GlobalRef, JMethodID};
+use jni::objects::JMethodID;
 use jni::JNIEnv;
 
 use std::mem;
 
-use crate::JniResult;
+use crate::{
+    utils::ref_metrics::{self, CountedRef},
+    JniResult,
+};
 
 /// A wrapper around Iterator used for constructing Java objects from their
 /// serialized representation. It allows to store the type information about
@@ -26,7 +29,7 @@ pub struct PairIter<InnerIter: Iterator> {
     /// The base `Iterator` instance.
     pub iter: InnerIter,
     /// Class of the Java representation of the iterator elements.
-    pub element_class: GlobalRef,
+    pub element_class: CountedRef,
     /// Cached method Id of the `element_class` constructor.
     pub constructor_id: JMethodID<'static>,
 }
@@ -38,7 +41,7 @@ impl<InnerIter: Iterator> PairIter<InnerIter> {
     /// i.e. `ClassName(byte[], byte[])`.
     pub fn new(env: &JNIEnv, iter: InnerIter, class_name: &str) -> JniResult<Self> {
         let class = env.find_class(class_name)?;
-        let element_class = env.new_global_ref(class)?;
+        let element_class = ref_metrics::new_global_ref(env, class.into())?;
         let signature = "([B[B)V";
         let id = env.get_method_id(class_name, "<init>", signature)?;
         Ok(PairIter {
@@ -25,7 +25,7 @@ use std::panic;
 
 use crate::{
     handle,
-    utils::{convert_to_string, unwrap_exc_or_default},
+    utils::{convert_to_string, jni_cache, unwrap_exc_or_default},
     Handle,
 };
 
@@ -40,6 +40,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_Prefixed_na
     access_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let access = handle::cast_handle::<ErasedAccess<'static>>(access_handle);
         let namespace = convert_to_string(&env, namespace)?;
         let prefixed = match access {
@@ -29,7 +29,7 @@ use jni::{
 use crate::{
     handle::{self, Handle},
     storage::Value,
-    utils,
+    utils::{self, jni_cache},
 };
 
 type Index = ProofListIndex<GenericRawAccess<'static>, Value>;
@@ -46,6 +46,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     access_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let address = utils::convert_to_index_address(&env, name, id_in_group)?;
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         let index: Index = access.get_proof_list(address);
@@ -73,6 +74,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     index: jlong,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let value = list.get(index as u64);
         utils::optional_array_to_java(&env, value)
@@ -88,6 +90,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     list_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let value = list.last();
         utils::optional_array_to_java(&env, value)
@@ -103,6 +106,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     list_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let value = list.pop();
         utils::optional_array_to_java(&env, value)
@@ -119,6 +123,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     len: jlong,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         list.truncate(len as u64);
         Ok(())
@@ -134,6 +139,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     list_handle: Handle,
 ) -> jboolean {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let is_empty = list.is_empty();
         Ok(is_empty as jboolean)
@@ -148,7 +154,10 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     _: JObject,
     list_handle: Handle,
 ) -> jlong {
-    let res = panic::catch_unwind(|| Ok(get_list_length(list_handle) as jlong));
+    let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
+        Ok(get_list_length(list_handle) as jlong)
+    });
     utils::unwrap_exc_or_default(&env, res)
 }
 
@@ -165,6 +174,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     list_handle: Handle,
 ) -> jint {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let height = list.height();
         Ok(i32::from(height))
@@ -180,6 +190,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     list_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let hash = list.object_hash();
         utils::convert_hash(&env, &hash)
@@ -197,6 +208,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     index: jlong,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let proof = list.get_proof(index as u64);
         utils::proto_to_java_bytes(&env, &proof)
@@ -215,6 +227,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     to: jlong,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let proof = list.get_range_proof(from as u64..to as u64);
         utils::proto_to_java_bytes(&env, &proof)
@@ -230,6 +243,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     list_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let iter = list.iter();
         Ok(handle::to_handle(iter))
@@ -246,6 +260,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     index_from: jlong,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let iter = list.iter_from(index_from as u64);
         Ok(handle::to_handle(iter))
@@ -262,6 +277,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     value: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let value = env.convert_byte_array(value)?;
         list.push(value);
@@ -280,6 +296,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     value: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         let value = env.convert_byte_array(value)?;
         list.set(index as u64, value);
@@ -296,6 +313,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     list_handle: Handle,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let list = handle::cast_handle::<Index>(list_handle);
         list.clear();
         Ok(())
@@ -311,6 +329,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofListInd
     iter_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iter = handle::cast_handle::<Iter>(iter_handle);
         utils::optional_array_to_java(&env, iter.next())
     });
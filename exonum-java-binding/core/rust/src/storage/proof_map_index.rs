@@ -31,7 +31,8 @@ use protobuf::Message;
 use crate::{
     handle::{self, Handle},
     storage::{Key, PairIter, Value},
-    utils, JniResult,
+    utils::{self, jni_cache},
+    JniResult,
 };
 
 type RawKey = [u8; PROOF_MAP_KEY_SIZE];
@@ -99,6 +100,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     key_hashing: jboolean,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let address = utils::convert_to_index_address(&env, name, id_in_group)?;
         let key_is_hashed = key_hashing == JNI_TRUE;
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
@@ -130,6 +132,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     map_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let hash = match map {
             Index::Raw(map) => map.object_hash(),
@@ -149,6 +152,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     key: jbyteArray,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let value = match map {
@@ -169,6 +173,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     key: jbyteArray,
 ) -> jboolean {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let contains = match map {
@@ -189,6 +194,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     key: jbyteArray,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let proof_proto = match map {
@@ -210,6 +216,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     keys: jobjectArray,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let keys = convert_to_keys(&env, keys)?;
         let proof_proto = match map {
@@ -230,6 +237,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     map_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let iter = match map {
             Index::Raw(map) => Iter::Raw(create_pair_iter(&env, map.iter())?),
@@ -248,6 +256,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     map_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let iter = match map {
             Index::Raw(map) => KeysIter::Raw(map.keys()),
@@ -266,6 +275,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     map_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let values = match map {
             Index::Raw(map) => map.values(),
@@ -285,6 +295,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     key: jbyteArray,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let iter = match map {
@@ -305,6 +316,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     key: jbyteArray,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let iter = match map {
@@ -325,6 +337,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     key: jbyteArray,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let values = match map {
@@ -346,6 +359,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     value: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         let value = env.convert_byte_array(value)?;
@@ -367,6 +381,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     key: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = env.convert_byte_array(key)?;
         match map {
@@ -386,6 +401,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     map_handle: Handle,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         match map {
             Index::Raw(map) => map.clear(),
@@ -404,6 +420,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     iter_handle: Handle,
 ) -> jobject {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iterWrapper = handle::cast_handle::<Iter>(iter_handle);
 
         let result = match iterWrapper {
@@ -412,7 +429,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
                     &env,
                     &arr[..],
                     &val,
-                    &wrapper.element_class,
+                    &*wrapper.element_class,
                     wrapper.constructor_id,
                 )
             }),
@@ -421,7 +438,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
                     &env,
                     key.as_slice(),
                     &val,
-                    &wrapper.element_class,
+                    &*wrapper.element_class,
                     wrapper.constructor_id,
                 )
             }),
@@ -450,6 +467,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     iter_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let value = match *handle::cast_handle::<KeysIter>(iter_handle) {
             KeysIter::Raw(ref mut iter) => utils::optional_array_to_java(&env, iter.next())?,
             KeysIter::Hashed(ref mut iter) => utils::optional_array_to_java(&env, iter.next())?,
@@ -477,6 +495,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ProofMapInde
     iter_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iter = handle::cast_handle::<ValuesIter>(iter_handle);
         utils::optional_array_to_java(&env, iter.next())
     });
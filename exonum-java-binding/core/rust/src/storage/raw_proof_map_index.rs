@@ -29,7 +29,8 @@ use jni::{
 use crate::{
     handle::{self, Handle},
     storage::{PairIter, Value},
-    utils, JniResult,
+    utils::{self, jni_cache},
+    JniResult,
 };
 
 type Key = [u8; PROOF_MAP_KEY_SIZE];
@@ -51,6 +52,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     access_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let address = utils::convert_to_index_address(&env, name, id_in_group)?;
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         let index: Index = access.get_raw_proof_map(address);
@@ -77,6 +79,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     map_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let hash = map.object_hash();
         utils::convert_hash(&env, &hash)
@@ -93,6 +96,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     key: jbyteArray,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = convert_to_key(&env, key)?;
         let value = map.get(&key);
@@ -110,6 +114,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     key: jbyteArray,
 ) -> jboolean {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = convert_to_key(&env, key)?;
         let contains = map.contains(&key);
@@ -127,6 +132,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     key: jbyteArray,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = convert_to_key(&env, key)?;
         let proof = map.get_proof(key);
@@ -144,6 +150,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     keys: jobjectArray,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let keys = utils::java_arrays_to_rust(&env, keys, convert_to_key)?;
         let proof = map.get_multiproof(keys);
@@ -160,6 +167,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     map_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let iter = map.iter();
         let iter = Iter::new(&env, iter, JAVA_ENTRY_FQN)?;
@@ -176,6 +184,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     map_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let keys = map.keys();
         Ok(handle::to_handle(keys))
@@ -191,6 +200,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     map_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let values = map.values();
         Ok(handle::to_handle(values))
@@ -207,6 +217,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     key: jbyteArray,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = convert_to_key(&env, key)?;
         let iter = map.iter_from(&key);
@@ -225,6 +236,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     key: jbyteArray,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = convert_to_key(&env, key)?;
         let keys = map.keys_from(&key);
@@ -242,6 +254,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     key: jbyteArray,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = convert_to_key(&env, key)?;
         let values = map.values_from(&key);
@@ -260,6 +273,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     value: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = convert_to_key(&env, key)?;
         let value = env.convert_byte_array(value)?;
@@ -278,6 +292,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     key: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         let key = convert_to_key(&env, key)?;
         map.remove(&key);
@@ -294,6 +309,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     map_handle: Handle,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let map = handle::cast_handle::<Index>(map_handle);
         map.clear();
         Ok(())
@@ -309,6 +325,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     iter_handle: Handle,
 ) -> jobject {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iterWrapper = handle::cast_handle::<Iter>(iter_handle);
         match iterWrapper.iter.next() {
             Some(val) => {
@@ -316,7 +333,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
                 let value = env.byte_array_from_slice(&val.1)?;
                 Ok(env
                     .new_object_unchecked(
-                        &iterWrapper.element_class,
+                        &*iterWrapper.element_class,
                         iterWrapper.constructor_id,
                         &[key.into(), value.into()],
                     )?
@@ -346,6 +363,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     iter_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iter = handle::cast_handle::<KeysIter>(iter_handle);
         utils::optional_array_to_java(&env, iter.next())
     });
@@ -370,6 +388,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_RawProofMapI
     iter_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iter = handle::cast_handle::<ValuesIter>(iter_handle);
         utils::optional_array_to_java(&env, iter.next())
     });
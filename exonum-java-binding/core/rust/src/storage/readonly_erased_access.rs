@@ -20,7 +20,11 @@ use jni::{objects::JClass, JNIEnv};
 
 use std::panic;
 
-use crate::{handle, into_erased_access, utils::unwrap_exc_or_default, Handle};
+use crate::{
+    handle, into_erased_access,
+    utils::{jni_cache, unwrap_exc_or_default},
+    Handle,
+};
 
 /// Creates a readonly access from the base access (passed as `access_handle`).
 ///
@@ -32,6 +36,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_RoErasedAcc
     access_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         let readonly_access = match access {
             GenericAccess::Raw(raw) => {
@@ -23,7 +23,7 @@ use std::panic;
 use crate::{
     handle::{self, Handle},
     storage::into_erased_access,
-    utils,
+    utils::{self, jni_cache},
 };
 
 /// Returns pointer to created `TemporaryDB` object.
@@ -32,7 +32,10 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_TemporaryDb
     env: JNIEnv,
     _: JClass,
 ) -> Handle {
-    let res = panic::catch_unwind(|| Ok(handle::to_handle(TemporaryDB::new())));
+    let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
+        Ok(handle::to_handle(TemporaryDB::new()))
+    });
     utils::unwrap_exc_or_default(&env, res)
 }
 
@@ -54,6 +57,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_TemporaryDb
     db_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let db = handle::cast_handle::<TemporaryDB>(db_handle);
         let access = unsafe { into_erased_access(db.snapshot()) };
         Ok(handle::to_handle(access))
@@ -69,6 +73,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_TemporaryDb
     db_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let db = handle::cast_handle::<TemporaryDB>(db_handle);
         let access = unsafe { into_erased_access(db.fork()) };
         Ok(handle::to_handle(access))
@@ -87,6 +92,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_database_TemporaryDb
     patch_handle: Handle,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let db = handle::cast_handle::<TemporaryDB>(db_handle);
         let patch = handle::acquire_handle_ownership::<Patch>(patch_handle);
         db.merge(*patch).expect("Unable to merge patch");
@@ -32,7 +32,7 @@ use jni::{
 use crate::{
     handle::{self, Handle},
     storage::{PairIter, Value},
-    utils,
+    utils::{self, jni_cache},
 };
 
 type Index = ValueSetIndex<GenericRawAccess<'static>, Value>;
@@ -53,6 +53,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ValueSetInde
     access_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let address = utils::convert_to_index_address(&env, name, id_in_group)?;
         let access = handle::cast_handle::<ErasedAccess>(access_handle);
         let index: Index = access.get_value_set(address);
@@ -113,6 +114,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ValueSetInde
     set_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let set = handle::cast_handle::<Index>(set_handle);
         let iter = set.iter();
         let iter = Iter::new(&env, iter, JAVA_ENTRY_FQN)?;
@@ -130,6 +132,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ValueSetInde
     from: jbyteArray,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let set = handle::cast_handle::<Index>(set_handle);
         let from = utils::convert_to_hash(&env, from)?;
         let iter = set.iter_from(&from);
@@ -147,6 +150,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ValueSetInde
     set_handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let set = handle::cast_handle::<Index>(set_handle);
         let hashes = set.hashes();
         Ok(handle::to_handle(hashes))
@@ -163,6 +167,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ValueSetInde
     from: jbyteArray,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let set = handle::cast_handle::<Index>(set_handle);
         let from = utils::convert_to_hash(&env, from)?;
         let hashes = set.hashes_from(&from);
@@ -180,6 +185,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ValueSetInde
     value: jbyteArray,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let set = handle::cast_handle::<Index>(set_handle);
         let value = env.convert_byte_array(value)?;
         set.insert(value);
@@ -230,6 +236,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ValueSetInde
     set_handle: Handle,
 ) {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let set = handle::cast_handle::<Index>(set_handle);
         set.clear();
         Ok(())
@@ -245,6 +252,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ValueSetInde
     iter_handle: Handle,
 ) -> jobject {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iterWrapper = handle::cast_handle::<Iter>(iter_handle);
         match iterWrapper.iter.next() {
             Some(val) => {
@@ -252,7 +260,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ValueSetInde
                 let value = env.byte_array_from_slice(&val.1)?;
                 Ok(env
                     .new_object_unchecked(
-                        &iterWrapper.element_class,
+                        &*iterWrapper.element_class,
                         iterWrapper.constructor_id,
                         &[hash.into(), value.into()],
                     )?
@@ -282,6 +290,7 @@ pub extern "system" fn Java_com_exonum_binding_core_storage_indices_ValueSetInde
     iter_handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let iter = handle::cast_handle::<HashIter>(iter_handle);
         match iter.next() {
             Some(val) => utils::convert_hash(&env, &val),
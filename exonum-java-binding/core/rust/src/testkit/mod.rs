@@ -32,6 +32,7 @@ use exonum_testkit::{TestKit, TestKitBuilder};
 use exonum_time::{TimeProvider, TimeServiceFactory};
 use jni::{
     objects::{JClass, JObject, JValue},
+    signature::JavaType,
     sys::{jboolean, jbyteArray, jobjectArray, jshort},
     Executor, JNIEnv,
 };
@@ -40,7 +41,11 @@ use crate::{
     handle::{cast_handle, drop_handle, to_handle, Handle},
     proto,
     storage::into_erased_access,
-    utils::{convert_to_string, unwrap_exc_or, unwrap_exc_or_default},
+    utils::{
+        convert_to_bytes, convert_to_string,
+        jni_cache::{self, classes_refs, static_methods},
+        unwrap_exc_or, unwrap_exc_or_default,
+    },
     JavaRuntimeProxy, JniResult,
 };
 
@@ -50,7 +55,6 @@ use exonum_rust_runtime::spec::{ForeignSpec, Spec};
 mod time_provider;
 
 const KEYPAIR_CLASS: &str = "com/exonum/binding/common/crypto/KeyPair";
-const KEYPAIR_CTOR_SIGNATURE: &str = "([B[B)Lcom/exonum/binding/common/crypto/KeyPair;";
 const EMULATED_NODE_CLASS: &str = "com/exonum/binding/testkit/EmulatedNode";
 const EMULATED_NODE_CTOR_SIGNATURE: &str = "(ILcom/exonum/binding/common/crypto/KeyPair;)V";
 const TIME_PROVIDER_FIELD_TYPE: &str = "Lcom/exonum/binding/testkit/TimeProviderAdapter;";
@@ -83,6 +87,7 @@ pub extern "system" fn Java_com_exonum_binding_testkit_TestKit_nativeCreateTestK
     runtime_adapter: JObject,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let mut builder = if auditor == jni::sys::JNI_TRUE {
             TestKitBuilder::auditor()
         } else {
@@ -156,6 +161,7 @@ pub extern "system" fn Java_com_exonum_binding_testkit_TestKit_nativeCreateSnaps
     handle: Handle,
 ) -> Handle {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let testkit = cast_handle::<TestKit>(handle);
         testkit.poll_events();
         let snapshot = testkit.snapshot();
@@ -173,6 +179,7 @@ pub extern "system" fn Java_com_exonum_binding_testkit_TestKit_nativeCreateBlock
     handle: Handle,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let testkit = cast_handle::<TestKit>(handle);
         let block = testkit.create_block().header;
         serialize_block(&env, block)
@@ -191,6 +198,7 @@ pub extern "system" fn Java_com_exonum_binding_testkit_TestKit_nativeCreateBlock
     transactions: jobjectArray,
 ) -> jbyteArray {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let testkit = cast_handle::<TestKit>(handle);
         let transactions_count = env.get_array_length(transactions)?;
         let mut raw_transactions = Vec::with_capacity(transactions_count as usize);
@@ -198,7 +206,7 @@ pub extern "system" fn Java_com_exonum_binding_testkit_TestKit_nativeCreateBlock
             let serialized_tx_object =
                 env.auto_local(env.get_object_array_element(transactions, i as _)?);
             let serialized_tx: jbyteArray = serialized_tx_object.as_obj().into_inner();
-            let serialized_tx = env.convert_byte_array(serialized_tx)?;
+            let serialized_tx = convert_to_bytes(&env, serialized_tx)?;
             raw_transactions.push(BinaryValue::from_bytes(serialized_tx.into()).unwrap());
         }
         let block = testkit
@@ -217,6 +225,7 @@ pub extern "system" fn Java_com_exonum_binding_testkit_TestKit_nativeGetEmulated
     handle: Handle,
 ) -> JObject<'e> {
     let res = panic::catch_unwind(|| {
+        jni_cache::ensure_loaded(&env);
         let testkit = cast_handle::<TestKit>(handle);
         let emulated_node = testkit.us();
         // Validator id == -1 in case of auditor node.
@@ -253,10 +262,10 @@ fn serialize_block(env: &JNIEnv, block: Block) -> jni::errors::Result<jbyteArray
 fn create_java_keypair<'a>(env: &'a JNIEnv, keypair: KeyPair) -> jni::errors::Result<JValue<'a>> {
     let public_key_byte_array = env.byte_array_from_slice(&keypair.public_key()[..])?;
     let secret_key_byte_array = env.byte_array_from_slice(&keypair.secret_key()[..])?;
-    env.call_static_method(
-        KEYPAIR_CLASS,
-        "newInstance",
-        KEYPAIR_CTOR_SIGNATURE,
+    env.call_static_method_unchecked(
+        &classes_refs::key_pair(),
+        static_methods::key_pair_new_instance_id(),
+        JavaType::Object(KEYPAIR_CLASS.into()),
         &[secret_key_byte_array.into(), public_key_byte_array.into()],
     )
 }
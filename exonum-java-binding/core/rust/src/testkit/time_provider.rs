@@ -17,16 +17,16 @@
 use chrono::{DateTime, Utc};
 use exonum::merkledb::BinaryValue;
 use exonum_time::TimeProvider;
-use jni::{
-    objects::{GlobalRef, JObject},
-    Executor, JNIEnv,
-};
+use jni::{objects::JObject, Executor, JNIEnv};
 
-use crate::utils::unwrap_jni;
+use crate::utils::{
+    ref_metrics::{self, CountedRef},
+    unwrap_jni,
+};
 
 /// Wrapper around Java interface TimeProvider.
 pub struct JavaTimeProvider {
-    provider: GlobalRef,
+    provider: CountedRef,
     exec: Executor,
 }
 
@@ -39,7 +39,7 @@ impl std::fmt::Debug for JavaTimeProvider {
 impl JavaTimeProvider {
     pub fn new(exec: Executor, java_provider: JObject) -> Self {
         let provider =
-            unwrap_jni(exec.with_attached(|env: &JNIEnv| env.new_global_ref(java_provider)));
+            unwrap_jni(exec.with_attached(|env| ref_metrics::new_global_ref(env, java_provider)));
         Self { provider, exec }
     }
 }
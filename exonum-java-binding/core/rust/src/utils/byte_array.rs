@@ -0,0 +1,90 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Operations on Java byte arrays that avoid the intermediate copies.
+
+use jni::{
+    sys::{jbyteArray, JNI_ABORT},
+    JNIEnv,
+};
+
+use std::{ptr, slice};
+
+use crate::{utils::ByteArrayError, JniErrorKind, JniResult};
+
+/// Copies the Java byte array to a vector, reading the length once and the contents
+/// with a single `GetByteArrayRegion` call into the vector of that length.
+///
+/// Returns `NullByteArray` error if the array is null, like `convert_to_bytes`.
+pub fn to_vec(env: &JNIEnv, array: jbyteArray) -> Result<Vec<u8>, ByteArrayError> {
+    if array.is_null() {
+        return Err(ByteArrayError::NullByteArray);
+    }
+    let len = env.get_array_length(array)? as usize;
+    let mut bytes = vec![0_u8; len];
+    // Safe as `i8` and `u8` have the same size and alignment, and the slice covers
+    // exactly the initialized elements of the vector.
+    let buf = unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut i8, len) };
+    env.get_byte_array_region(array, 0, buf)?;
+    Ok(bytes)
+}
+
+/// Returns `true` if the arrays have the same contents, like `java.util.Arrays.equals`:
+/// two null arrays are equal, and a null array is not equal to any non-null one.
+///
+/// The contents are compared in place using `GetPrimitiveArrayCritical`, without copying.
+/// No other JNI functions are called while the arrays are held, as the critical region
+/// requires (the JVM may suspend the garbage collection for its duration).
+pub fn equals(env: &JNIEnv, a: jbyteArray, b: jbyteArray) -> JniResult<bool> {
+    if a.is_null() || b.is_null() {
+        return Ok(a.is_null() && b.is_null());
+    }
+    if env.is_same_object(a.into(), b.into())? {
+        return Ok(true);
+    }
+    // The lengths are read before entering the critical region.
+    let len_a = env.get_array_length(a)? as usize;
+    let len_b = env.get_array_length(b)? as usize;
+    if len_a != len_b {
+        return Ok(false);
+    }
+    if len_a == 0 {
+        return Ok(true);
+    }
+
+    let raw = env.get_native_interface();
+    // Safe as the arrays are valid (non-null) local references of the current thread,
+    // and only the critical functions are called until both arrays are released.
+    unsafe {
+        let get_critical = (**raw).GetPrimitiveArrayCritical.unwrap();
+        let release_critical = (**raw).ReleasePrimitiveArrayCritical.unwrap();
+
+        let data_a = get_critical(raw, a, ptr::null_mut());
+        if data_a.is_null() {
+            // `OutOfMemoryError` is thrown.
+            return Err(JniErrorKind::JavaException.into());
+        }
+        let data_b = get_critical(raw, b, ptr::null_mut());
+        if data_b.is_null() {
+            release_critical(raw, a, data_a, JNI_ABORT);
+            return Err(JniErrorKind::JavaException.into());
+        }
+        let equal = slice::from_raw_parts(data_a as *const u8, len_a)
+            == slice::from_raw_parts(data_b as *const u8, len_b);
+        // The arrays are not modified, so there is nothing to copy back.
+        release_critical(raw, b, data_b, JNI_ABORT);
+        release_critical(raw, a, data_a, JNI_ABORT);
+        Ok(equal)
+    }
+}
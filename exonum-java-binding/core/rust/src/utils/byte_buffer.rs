@@ -0,0 +1,65 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Passing of the native memory to Java and back as direct `ByteBuffer`s, without copying.
+
+use jni::{
+    objects::{JByteBuffer, JObject},
+    JNIEnv,
+};
+
+use std::slice;
+
+use crate::{JniErrorKind, JniResult};
+
+/// Creates a direct `ByteBuffer` viewing `len` bytes of the native memory at `data`.
+///
+/// # Safety
+///
+/// The buffer does not own the memory: it must outlive the Java buffer and all its views
+/// (which may be retained by the Java code past the native call), otherwise
+/// the Java code will access freed memory. The memory must not be accessed mutably
+/// on the native side while the Java code may access it.
+pub unsafe fn new_direct<'a>(
+    env: &JNIEnv<'a>,
+    data: *mut u8,
+    len: usize,
+) -> JniResult<JByteBuffer<'a>> {
+    env.new_direct_byte_buffer(slice::from_raw_parts_mut(data, len))
+}
+
+/// Returns the memory of the given direct `ByteBuffer`.
+///
+/// Returns an error if the buffer is not direct.
+///
+/// # Safety
+///
+/// The memory is owned by the buffer (if allocated by Java, e.g., with
+/// `ByteBuffer.allocateDirect`) or by the native code that created it (see `new_direct`),
+/// not by the returned slice. The caller must ensure that the memory is not released
+/// (for buffers allocated by Java — that the buffer is reachable) and not modified
+/// while the slice is used.
+pub unsafe fn from_direct<'a>(env: &JNIEnv<'a>, buffer: JByteBuffer<'a>) -> JniResult<&'a [u8]> {
+    let raw = env.get_native_interface();
+    let buffer = JObject::from(buffer).into_inner();
+    let data = (**raw).GetDirectBufferAddress.unwrap()(raw, buffer) as *const u8;
+    if data.is_null() {
+        return Err(JniErrorKind::NullPtr("Direct buffer address").into());
+    }
+    let capacity = (**raw).GetDirectBufferCapacity.unwrap()(raw, buffer);
+    if capacity < 0 {
+        return Err(JniErrorKind::Msg("Buffer is not direct".to_owned()).into());
+    }
+    Ok(slice::from_raw_parts(data, capacity as usize))
+}
@@ -19,9 +19,55 @@ use jni::sys::{jbyteArray, jobjectArray};
 use jni::JNIEnv;
 use protobuf::Message;
 
-use std::ptr;
+use std::{error::Error, fmt, ptr};
 
-use crate::JniResult;
+use crate::{JniError, JniErrorKind, JniResult};
+
+/// Error of the conversion of a Java byte array to bytes, see `convert_to_bytes`.
+#[derive(Debug)]
+pub enum ByteArrayError {
+    /// The array is null (unlike an empty array, which is converted to an empty vector).
+    NullByteArray,
+    /// JNI error occurred while reading the array.
+    Jni(JniError),
+}
+
+impl fmt::Display for ByteArrayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ByteArrayError::NullByteArray => write!(f, "Byte array is null"),
+            ByteArrayError::Jni(e) => write!(f, "Failed to read byte array: {}", e),
+        }
+    }
+}
+
+impl Error for ByteArrayError {}
+
+impl From<JniError> for ByteArrayError {
+    fn from(e: JniError) -> Self {
+        ByteArrayError::Jni(e)
+    }
+}
+
+impl From<ByteArrayError> for JniError {
+    fn from(e: ByteArrayError) -> Self {
+        match e {
+            ByteArrayError::NullByteArray => JniErrorKind::NullPtr("byte array").into(),
+            ByteArrayError::Jni(e) => e,
+        }
+    }
+}
+
+/// Converts Java byte array to bytes. The elements are copied as is, i.e., the negative Java
+/// bytes become the bytes above `0x7f` (`-1` is `0xff`).
+///
+/// Returns `NullByteArray` error if the array is null.
+pub fn convert_to_bytes(env: &JNIEnv, array: jbyteArray) -> Result<Vec<u8>, ByteArrayError> {
+    if array.is_null() {
+        return Err(ByteArrayError::NullByteArray);
+    }
+    Ok(env.convert_byte_array(array)?)
+}
 
 /// Converts Java byte array to `Hash`. Panics if array has the wrong length.
 pub fn convert_to_hash(env: &JNIEnv, array: jbyteArray) -> JniResult<Hash> {
@@ -103,3 +149,17 @@ pub fn optional_array_to_java<B: AsRef<[u8]>>(
         env.byte_array_from_slice(slice.as_ref())
     })
 }
+
+/// Formats the serialized artifact id as a lowercase hex string, e.g., `0a0b`,
+/// so that the artifact ids appear in the native logs in the same greppable form.
+pub fn format_artifact_id(id: &[u8]) -> String {
+    id.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Formats the artifact name and version as a Maven-like coordinate
+/// `groupId:artifactId:version`, e.g., `com.acme:land-registry:1.2.0`
+/// for the "com.acme/land-registry" artifact. The names without a group are
+/// kept as is.
+pub fn format_artifact_coordinate(name: &str, version: &str) -> String {
+    format!("{}:{}", name.replace('/', ":"), version)
+}
@@ -15,21 +15,59 @@
  */
 
 use exonum::merkledb::Error as DatabaseError;
-use jni::{objects::JObject, signature::JavaType, JNIEnv};
+use jni::{
+    objects::{JObject, JThrowable},
+    signature::JavaType,
+    sys::{jobjectArray, jsize},
+    JNIEnv,
+};
 use log::error;
 
 use std::{any::Any, cell::Cell, error::Error, result, thread};
 
 use crate::{
     utils::{
-        get_class_name, get_exception_message,
-        jni_cache::{classes_refs, throwable::get_cause_id},
+        convert_to_string, get_class_name, get_class_simple_name, get_exception_localized_message,
+        get_exception_message, is_instance_of_cached,
+        jni_cache::{
+            self, classes_refs,
+            execution_exception::new_execution_exception,
+            stack_trace_element,
+            throwable::{get_cause_id, get_stack_trace_id},
+            unexpected_execution_exception::throw_unexpected_execution_exception,
+        },
+        with_local_frame, DEFAULT_LOCAL_FRAME_CAPACITY,
     },
     JniError, JniErrorKind, JniResult,
 };
 use jni::objects::JValue;
 
 const JAVA_LANG_THROWABLE: &str = "java/lang/Throwable";
+const JAVA_LANG_STACK_TRACE_ELEMENT: &str = "java/lang/StackTraceElement";
+const JAVA_LANG_STRING: &str = "java/lang/String";
+
+/// The default maximum number of causes included by `describe_throwable`.
+pub const DEFAULT_MAX_CAUSE_DEPTH: usize = 16;
+/// The default maximum number of frames returned by `capture_stack_trace`.
+pub const DEFAULT_MAX_STACK_FRAMES: usize = 64;
+
+/// Throws `RuntimeException` with the message prefixed with the location in the Rust code
+/// the macro is invoked at, e.g., `[src/proxy/foo.rs:42] Failed to ...`, as the Java stack
+/// trace ends at the JNI boundary. The message is specified as `format!` arguments.
+///
+/// Evaluates to `JniResult<()>`, an error if the exception cannot be thrown
+/// (see `throw_for_kind`).
+#[macro_export]
+macro_rules! throw_runtime_exception {
+    ($env:expr, $($arg:tt)+) => {
+        $crate::utils::throw_for_kind(
+            $env,
+            $crate::utils::JavaErrorKind::Runtime,
+            &format!("[{}:{}] {}", file!(), line!(), format_args!($($arg)+)),
+            None,
+        )
+    };
+}
 
 /// Unwraps the result, returning its content.
 ///
@@ -138,6 +176,208 @@ pub fn describe_java_exception(env: &JNIEnv, exception: JObject) -> String {
     unwrap_jni_verbose(env, format())
 }
 
+/// Describes the throwable and its chain of causes, one per line, e.g.:
+///
+/// ```text
+/// com.example.FooException: message
+///   caused by: java.lang.NullPointerException
+/// ```
+///
+/// Includes at most `DEFAULT_MAX_CAUSE_DEPTH` causes, so that self-referential
+/// chains of causes do not lead to an infinite loop. Any Java exception occurred
+/// during the introspection is cleared.
+///
+/// Panics if `throwable` is null.
+pub fn describe_throwable(env: &JNIEnv, throwable: JThrowable) -> String {
+    describe_throwable_with_depth(env, throwable, DEFAULT_MAX_CAUSE_DEPTH)
+}
+
+/// Same as `describe_throwable`, but includes at most `max_depth` causes.
+pub fn describe_throwable_with_depth(
+    env: &JNIEnv,
+    throwable: JThrowable,
+    max_depth: usize,
+) -> String {
+    describe_throwable_chain(env, throwable, max_depth, DescriptionStyle::default())
+}
+
+/// Same as `describe_throwable`, but prefers the localized messages
+/// (`getLocalizedMessage`), falling back to `getMessage` if the localized message
+/// is null or cannot be obtained.
+pub fn describe_throwable_localized(env: &JNIEnv, throwable: JThrowable) -> String {
+    let style = DescriptionStyle {
+        localized: true,
+        ..DescriptionStyle::default()
+    };
+    describe_throwable_chain(env, throwable, DEFAULT_MAX_CAUSE_DEPTH, style)
+}
+
+/// Same as `describe_throwable`, but uses the simple class names (`Class#getSimpleName`)
+/// to make the description shorter, e.g., `ExecutionException: message`.
+/// Use `describe_throwable` when the fully-qualified names are needed.
+pub fn describe_throwable_simple(env: &JNIEnv, throwable: JThrowable) -> String {
+    let style = DescriptionStyle {
+        simple_names: true,
+        ..DescriptionStyle::default()
+    };
+    describe_throwable_chain(env, throwable, DEFAULT_MAX_CAUSE_DEPTH, style)
+}
+
+/// Defines how each throwable of the chain is described.
+#[derive(Clone, Copy, Default)]
+struct DescriptionStyle {
+    /// Whether to prefer the localized messages.
+    localized: bool,
+    /// Whether to use the simple class names instead of the fully-qualified ones.
+    simple_names: bool,
+}
+
+fn describe_throwable_chain(
+    env: &JNIEnv,
+    throwable: JThrowable,
+    max_depth: usize,
+    style: DescriptionStyle,
+) -> String {
+    let throwable: JObject = throwable.into();
+    assert!(!throwable.is_null(), "Throwable is null");
+
+    let mut description = String::new();
+    let mut current = throwable;
+    let mut depth = 0;
+    while !current.is_null() {
+        if depth > max_depth {
+            description.push_str("\n  ...");
+            break;
+        }
+        if depth > 0 {
+            description.push_str("\n  caused by: ");
+        }
+        let cause = describe_single_throwable(env, current, style).and_then(|single| {
+            description.push_str(&single);
+            get_exception_cause(env, current)
+        });
+        current = match cause {
+            Ok(cause) => cause,
+            Err(_) => {
+                // The description is best-effort, so the error is not propagated.
+                let _ = env.exception_clear();
+                description.push_str("<failed to describe>");
+                break;
+            }
+        };
+        depth += 1;
+    }
+    description
+}
+
+/// Returns the stack trace of the throwable, a frame per element in the form of
+/// `StackTraceElement#toString`, e.g., `com.example.Foo.bar(Foo.java:42)`.
+///
+/// Includes at most `DEFAULT_MAX_STACK_FRAMES` top frames. Any Java exception occurred
+/// during the introspection is cleared; the frames obtained before it are returned.
+///
+/// Panics if `throwable` is null.
+pub fn capture_stack_trace(env: &JNIEnv, throwable: JThrowable) -> Vec<String> {
+    capture_stack_trace_with_limit(env, throwable, DEFAULT_MAX_STACK_FRAMES)
+}
+
+/// Same as `capture_stack_trace`, but includes at most `max_frames` top frames.
+pub fn capture_stack_trace_with_limit(
+    env: &JNIEnv,
+    throwable: JThrowable,
+    max_frames: usize,
+) -> Vec<String> {
+    let throwable: JObject = throwable.into();
+    assert!(!throwable.is_null(), "Throwable is null");
+
+    let mut frames = Vec::new();
+    if read_stack_trace(env, throwable, max_frames, &mut frames).is_err() {
+        // The stack trace is best-effort, so the error is not propagated.
+        let _ = env.exception_clear();
+    }
+    frames
+}
+
+/// Appends at most `max_frames` frames of the throwable stack trace to `frames`.
+/// Each element is read in its own local frame, so that deep stacks do not overflow
+/// the local reference table.
+fn read_stack_trace(
+    env: &JNIEnv,
+    throwable: JObject,
+    max_frames: usize,
+    frames: &mut Vec<String>,
+) -> JniResult<()> {
+    let stack_trace: jobjectArray = env
+        .call_method_unchecked(
+            throwable,
+            get_stack_trace_id(),
+            JavaType::Array(Box::new(JavaType::Object(
+                JAVA_LANG_STACK_TRACE_ELEMENT.into(),
+            ))),
+            &[],
+        )?
+        .l()?
+        .into_inner();
+    let length = env.get_array_length(stack_trace)? as usize;
+    for i in 0..length.min(max_frames) {
+        let frame = with_local_frame(env, DEFAULT_LOCAL_FRAME_CAPACITY, || {
+            let element = env.get_object_array_element(stack_trace, i as jsize)?;
+            let frame = env
+                .call_method_unchecked(
+                    element,
+                    stack_trace_element::to_string_id(),
+                    JavaType::Object(JAVA_LANG_STRING.into()),
+                    &[],
+                )?
+                .l()?;
+            convert_to_string(env, frame)
+        })?;
+        frames.push(frame);
+    }
+    env.delete_local_ref(stack_trace.into())
+}
+
+/// Describes a single throwable in a form of "EXCEPTION_NAME: EXCEPTION_MESSAGE",
+/// omitting the message if it is null.
+fn describe_single_throwable(
+    env: &JNIEnv,
+    throwable: JObject,
+    style: DescriptionStyle,
+) -> JniResult<String> {
+    // The class and the message are released as soon as described, so that long
+    // cause chains do not overflow the local reference table.
+    with_local_frame(env, DEFAULT_LOCAL_FRAME_CAPACITY, || {
+        let class_name = if style.simple_names {
+            get_class_simple_name(env, throwable)?
+        } else {
+            get_class_name(env, throwable)?
+        };
+        let message = if style.localized {
+            get_localized_message_or_default(env, throwable)?
+        } else {
+            get_exception_message(env, throwable)?
+        };
+        let description = match message {
+            Some(message) => format!("{}: {}", class_name, message),
+            None => class_name,
+        };
+        Ok(description)
+    })
+}
+
+/// Returns the localized message of the throwable, falling back to its message if the
+/// localized one is null or `getLocalizedMessage` throws (the exception is cleared).
+fn get_localized_message_or_default(env: &JNIEnv, throwable: JObject) -> JniResult<Option<String>> {
+    match get_exception_localized_message(env, throwable) {
+        Ok(Some(message)) => Ok(Some(message)),
+        Ok(None) => get_exception_message(env, throwable),
+        Err(_) => {
+            env.exception_clear()?;
+            get_exception_message(env, throwable)
+        }
+    }
+}
+
 type ExceptionResult<T> = thread::Result<result::Result<T, JniError>>;
 
 /// Returns value or "throws" exception. `error_val` is returned, because exception will be thrown
@@ -201,7 +441,14 @@ pub fn get_exception_cause<'a>(env: &JNIEnv<'a>, exception: JObject<'a>) -> JniR
 /// the Java side.
 fn throw(env: &JNIEnv, error_message: &str) {
     // We cannot throw exception from this function, so errors should be written in log instead.
-    if let Err(e) = env.throw_new(&classes_refs::java_lang_runtime_exception(), error_message) {
+    // The cached classes are not available if the panic is caused by a call before
+    // the cache initialization (see `jni_cache::ensure_loaded`).
+    let result = if jni_cache::is_cache_initialized() {
+        throw_for_kind(env, JavaErrorKind::Runtime, error_message, None)
+    } else {
+        env.throw_new("java/lang/RuntimeException", error_message)
+    };
+    if let Err(e) = result {
         error!(
             "Failed to throw RuntimeException({}): {}",
             error_message,
@@ -210,6 +457,142 @@ fn throw(env: &JNIEnv, error_message: &str) {
     }
 }
 
+/// Category of a Java throwable, see `classify_exception`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ExceptionKind {
+    /// `ExecutionException` (or its subclass).
+    Execution,
+    /// `UnexpectedExecutionException`.
+    UnexpectedExecution,
+    /// `IllegalArgumentException`.
+    IllegalArgument,
+    /// `IllegalStateException`.
+    IllegalState,
+    /// `NoSuchElementException`.
+    NoSuchElement,
+    /// `IndexOutOfBoundsException`.
+    IndexOutOfBounds,
+    /// Any other `RuntimeException`.
+    Runtime,
+    /// `java.lang.Error`.
+    Error,
+    /// Any other throwable, e.g., a checked exception.
+    Other,
+}
+
+/// Returns the category of the throwable, checking it against the cached exception classes
+/// from the most specific one.
+///
+/// Panics if `throwable` is null or there is some JNI error.
+pub fn classify_exception(env: &JNIEnv, throwable: JThrowable) -> ExceptionKind {
+    let throwable: JObject = throwable.into();
+    assert!(!throwable.is_null(), "Throwable is null");
+
+    classes_refs::all()
+        .iter()
+        .find(|(_, class)| is_instance_of_cached(env, throwable, class))
+        .map_or(ExceptionKind::Other, |(name, _)| exception_kind(name))
+}
+
+/// Returns the category of the cached exception class with the given JNI name.
+fn exception_kind(class_name: &str) -> ExceptionKind {
+    match class_name {
+        "com/exonum/binding/core/service/ExecutionException" => ExceptionKind::Execution,
+        "com/exonum/binding/core/runtime/UnexpectedExecutionException" => {
+            ExceptionKind::UnexpectedExecution
+        }
+        "java/lang/IllegalArgumentException" => ExceptionKind::IllegalArgument,
+        "java/lang/IllegalStateException" => ExceptionKind::IllegalState,
+        "java/util/NoSuchElementException" => ExceptionKind::NoSuchElement,
+        "java/lang/IndexOutOfBoundsException" => ExceptionKind::IndexOutOfBounds,
+        "java/lang/RuntimeException" => ExceptionKind::Runtime,
+        "java/lang/Error" => ExceptionKind::Error,
+        _ => ExceptionKind::Other,
+    }
+}
+
+/// Throws `IllegalStateException` with the given message, e.g., when a service method
+/// is called in the wrong lifecycle phase.
+pub fn throw_illegal_state(env: &JNIEnv, message: &str) -> JniResult<()> {
+    throw_for_kind(env, JavaErrorKind::IllegalState, message, None)
+}
+
+/// Java exception class thrown by the native code, see `throw_for_kind`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum JavaErrorKind {
+    /// `java.lang.Error`.
+    Error,
+    /// `RuntimeException`.
+    Runtime,
+    /// `IllegalArgumentException`.
+    IllegalArgument,
+    /// `IllegalStateException`.
+    IllegalState,
+    /// `ExecutionException` with an error code.
+    Execution,
+    /// `UnexpectedExecutionException` caused by a `RuntimeException` with the message.
+    UnexpectedExecution,
+}
+
+/// Throws the exception of the given kind with the given message, using the cached
+/// exception classes.
+///
+/// The error code is required for `ExecutionException` and ignored for other kinds.
+/// Returns an error if `ExecutionException` is requested without a code or the exception
+/// cannot be constructed.
+pub fn throw_for_kind(
+    env: &JNIEnv,
+    kind: JavaErrorKind,
+    message: &str,
+    code: Option<i8>,
+) -> JniResult<()> {
+    let class = match kind {
+        JavaErrorKind::Error => classes_refs::java_lang_error(),
+        JavaErrorKind::Runtime => classes_refs::java_lang_runtime_exception(),
+        JavaErrorKind::IllegalArgument => classes_refs::java_lang_illegal_argument_exception(),
+        JavaErrorKind::IllegalState => classes_refs::java_lang_illegal_state_exception(),
+        JavaErrorKind::Execution => {
+            let code = code.ok_or_else(|| {
+                JniError::from(JniErrorKind::Msg(
+                    "ExecutionException requires an error code".to_owned(),
+                ))
+            })?;
+            let exception = new_execution_exception(env, code, message)?;
+            return env.throw(exception);
+        }
+        JavaErrorKind::UnexpectedExecution => {
+            return throw_unexpected_execution_exception(env, message);
+        }
+    };
+    env.throw_new(&class, message)
+}
+
+/// Throws `UnexpectedExecutionException` describing the Rust panic caught with
+/// `panic::catch_unwind`, so that the panic does not cross the JNI boundary.
+///
+/// The message of the exception (of its cause) is the panic message (see `any_to_string`)
+/// prefixed with "Rust panic: ". Falls back to `RuntimeException` if the exception
+/// cannot be constructed or the JNI cache is not initialized. A pending Java exception,
+/// if any, is replaced. Errors are logged, as the function is used on the error path.
+pub fn throw_rust_panic(env: &JNIEnv, payload: Box<dyn Any + Send>) {
+    let message = format!("Rust panic: {}", any_to_string(&payload));
+    if env.exception_check().unwrap_or(false) {
+        let _ = env.exception_clear();
+    }
+    if jni_cache::is_cache_initialized() {
+        match throw_unexpected_execution_exception(env, &message) {
+            Ok(()) => return,
+            Err(e) => {
+                error!("Failed to throw UnexpectedExecutionException: {}", e);
+                let _ = env.exception_clear();
+            }
+        }
+    }
+    if let Err(e) = env.throw_new("java/lang/RuntimeException", &message) {
+        error!("Failed to throw RuntimeException({}): {}", message, e);
+    }
+}
+
 /// Tries to get meaningful description from panic-error.
 pub fn any_to_string(any: &Box<dyn Any + Send>) -> String {
     if let Some(s) = any.downcast_ref::<&str>() {
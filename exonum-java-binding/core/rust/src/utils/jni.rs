@@ -14,14 +14,15 @@
  * limitations under the License.
  */
 
-use jni::objects::JObject;
-use jni::signature::JavaType;
+use jni::objects::{GlobalRef, JClass, JMethodID, JObject, JValue};
+use jni::signature::{JavaType, Primitive};
 use jni::JNIEnv;
 
 use crate::{
     utils::{
         convert_to_string,
         jni_cache::{class, object, throwable},
+        unwrap_jni,
     },
     JniResult,
 };
@@ -29,8 +30,65 @@ use crate::{
 const RETVAL_TYPE_STRING: &str = "java/lang/String";
 const RETVAL_TYPE_CLASS: &str = "java/lang/Class";
 
+/// The default capacity of the local reference frame created by `with_local_frame`,
+/// which is the number of local references the JVM guarantees to be available
+/// in a native method.
+pub const DEFAULT_LOCAL_FRAME_CAPACITY: i32 = 16;
+
+/// Executes `f` in a new local reference frame guaranteed to fit at least `capacity`
+/// local references (the JVM may allow more), and releases all the local references
+/// created by `f` when it returns, successfully or not, or panics.
+///
+/// Intended for the loops over many Java objects, where the local references may otherwise
+/// overflow the local reference table. The `JObject`s created inside the frame are invalid
+/// after it is popped: `f` shall return Rust values (e.g., `String`), or promote the objects
+/// to `GlobalRef`s to use them outside the frame.
+pub fn with_local_frame<F, R>(env: &JNIEnv, capacity: i32, f: F) -> JniResult<R>
+where
+    F: FnOnce() -> JniResult<R>,
+{
+    env.push_local_frame(capacity)?;
+    let frame = LocalFrame { env, popped: false };
+    let result = f();
+    frame.pop()?;
+    result
+}
+
+/// Pops the local reference frame pushed by `with_local_frame` when dropped,
+/// so that the frame is popped if its function panics.
+struct LocalFrame<'a, 'b> {
+    env: &'a JNIEnv<'b>,
+    popped: bool,
+}
+
+impl LocalFrame<'_, '_> {
+    fn pop(mut self) -> JniResult<()> {
+        self.popped = true;
+        self.env.pop_local_frame(JObject::null()).map(|_| ())
+    }
+}
+
+impl Drop for LocalFrame<'_, '_> {
+    fn drop(&mut self) {
+        if !self.popped {
+            // Errors are ignored, as the frame is popped during unwinding.
+            let _ = self.env.pop_local_frame(JObject::null());
+        }
+    }
+}
+
 /// Returns a class name of an obj as a `String`.
 pub fn get_class_name(env: &JNIEnv, obj: JObject) -> JniResult<String> {
+    call_class_name_method(env, obj, class::get_name_id())
+}
+
+/// Returns a simple class name of an obj (`Class#getSimpleName`, e.g., `ExecutionException`)
+/// as a `String`.
+pub fn get_class_simple_name(env: &JNIEnv, obj: JObject) -> JniResult<String> {
+    call_class_name_method(env, obj, class::get_simple_name_id())
+}
+
+fn call_class_name_method(env: &JNIEnv, obj: JObject, name_id: JMethodID) -> JniResult<String> {
     let class_object = env
         .call_method_unchecked(
             obj,
@@ -43,7 +101,7 @@ pub fn get_class_name(env: &JNIEnv, obj: JObject) -> JniResult<String> {
     let class_name = env
         .call_method_unchecked(
             class_object,
-            class::get_name_id(),
+            name_id,
             JavaType::Object(RETVAL_TYPE_STRING.into()),
             &[],
         )?
@@ -51,6 +109,62 @@ pub fn get_class_name(env: &JNIEnv, obj: JObject) -> JniResult<String> {
     convert_to_string(env, class_name)
 }
 
+/// Describes an arbitrary object using `Object#toString`, e.g., for logging unexpected
+/// values returned from the Java side. Returns `null` for the null object.
+///
+/// The pending Java exception, if any, is not affected: the object is not described
+/// in this case, as no Java code may be called. An exception thrown by `toString`
+/// is cleared.
+pub fn describe_object(env: &JNIEnv, obj: JObject) -> String {
+    const UNDESCRIBED: &str = "<failed to describe>";
+    if obj.is_null() {
+        return "null".to_owned();
+    }
+    match env.exception_check() {
+        Ok(false) => {}
+        _ => return UNDESCRIBED.to_owned(),
+    }
+    env.call_method_unchecked(
+        obj,
+        object::to_string_id(),
+        JavaType::Object(RETVAL_TYPE_STRING.into()),
+        &[],
+    )
+    .and_then(JValue::l)
+    .and_then(|description| {
+        if description.is_null() {
+            Ok("null".to_owned())
+        } else {
+            convert_to_string(env, description)
+        }
+    })
+    .unwrap_or_else(|_| {
+        let _ = env.exception_clear();
+        UNDESCRIBED.to_owned()
+    })
+}
+
+/// Returns `true` if the object is an instance of the cached class (e.g., one of
+/// `jni_cache::classes_refs`) or its subclass. Unlike `IsInstanceOf`, returns `false`
+/// for the null object.
+///
+/// Panics if there is some JNI error.
+pub fn is_instance_of_cached(env: &JNIEnv, obj: JObject, cached_class: &GlobalRef) -> bool {
+    !obj.is_null() && unwrap_jni(env.is_instance_of(obj, cached_class))
+}
+
+/// Returns `true` if the `superclass` is the same as or a superclass (superinterface)
+/// of the `class`, using the cached `Class.isAssignableFrom`.
+pub fn is_assignable_from(env: &JNIEnv, class: JClass, superclass: JClass) -> JniResult<bool> {
+    env.call_method_unchecked(
+        superclass,
+        class::is_assignable_from_id(),
+        JavaType::Primitive(Primitive::Boolean),
+        &[JValue::from(JObject::from(class))],
+    )?
+    .z()
+}
+
 /// Returns the message from the exception if it is not null.
 ///
 /// `exception` should extend `java.lang.Throwable` and be not null
@@ -68,3 +182,24 @@ pub fn get_exception_message(env: &JNIEnv, exception: JObject) -> JniResult<Opti
     }
     convert_to_string(env, message).map(Some)
 }
+
+/// Returns the localized message from the exception if it is not null.
+///
+/// `exception` should extend `java.lang.Throwable` and be not null
+pub fn get_exception_localized_message(
+    env: &JNIEnv,
+    exception: JObject,
+) -> JniResult<Option<String>> {
+    assert!(!exception.is_null(), "Invalid exception argument");
+    let message = env.call_method_unchecked(
+        exception,
+        throwable::get_localized_message_id(),
+        JavaType::Object(RETVAL_TYPE_STRING.into()),
+        &[],
+    )?;
+    let message = message.l()?;
+    if message.is_null() {
+        return Ok(None);
+    }
+    convert_to_string(env, message).map(Some)
+}
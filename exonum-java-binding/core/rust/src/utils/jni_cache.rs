@@ -14,311 +14,4025 @@
 
 //! Caching some of the often used methods and classes helps to improve
 //! performance. Caching is done immediately after loading of the native
-//! library by JVM. To do so, we use JNI_OnLoad method. JNI_OnUnload is not
-//! currently used because we don't need to reload native library multiple times
-//! during execution.
+//! library by JVM. To do so, we use JNI_OnLoad method. JNI_OnUnload releases
+//! the cached references, so that the library can be loaded again (e.g., by
+//! another JVM created in the same process) and re-cache them.
 //!
 //! See: https://docs.oracle.com/en/java/javase/12/docs/specs/jni/invocation.html#jni_onload
 
-use std::{os::raw::c_void, panic::catch_unwind};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    cmp, env,
+    error::Error,
+    fmt,
+    os::raw::c_void,
+    panic::{self, catch_unwind, UnwindSafe},
+    ptr,
+    sync::{
+        atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use backtrace::Backtrace;
 
 use jni::{
-    objects::{GlobalRef, JMethodID},
-    sys::{jint, JNI_VERSION_1_8},
+    objects::{GlobalRef, JClass, JFieldID, JMethodID, JObject, JStaticMethodID, JString, JValue},
+    signature::JavaType,
+    sys::{_jmethodID, jint, jstring, JNI_OK, JNI_VERSION_1_8},
     JNIEnv, JavaVM,
 };
-use log::debug;
-use parking_lot::Once;
+use log::{debug, error, trace, warn};
+
+use crate::{
+    utils::{
+        any_to_string, convert_to_string,
+        ref_metrics::{self, CountedRef},
+        unwrap_exc_or, with_local_frame, DEFAULT_LOCAL_FRAME_CAPACITY,
+    },
+    JniError, JniErrorKind, JniResult,
+};
+use parking_lot::{const_mutex, Mutex};
 
 /// Invalid JNI version constant, signifying JNI_OnLoad failure.
 const INVALID_JNI_VERSION: jint = 0;
+/// JNI version of Java 9.
+const JNI_VERSION_9: jint = 0x0009_0000;
+/// JNI version of Java 10.
+const JNI_VERSION_10: jint = 0x000a_0000;
+/// Name of the environment variable requesting a JNI version other than the default (1.8).
+/// Supported values are `1.8`, `9` and `10`.
+const JNI_VERSION_ENV: &str = "EJB_JNI_VERSION";
+const JAVA_LANG_CLASS_NAME: &str = "java/lang/Class";
+const JAVA_LANG_THREAD_CLASS: &str = "java/lang/Thread";
+const JAVA_LANG_SYSTEM_CLASS: &str = "java/lang/System";
+const JAVA_LANG_INTEGER_CLASS: &str = "java/lang/Integer";
+const JAVA_LANG_LONG_CLASS: &str = "java/lang/Long";
+const JAVA_LANG_BOOLEAN_CLASS: &str = "java/lang/Boolean";
+const JAVA_UTIL_ARRAY_LIST_CLASS: &str = "java/util/ArrayList";
+const JAVA_UTIL_LIST_CLASS: &str = "java/util/List";
+const JAVA_UTIL_MAP_ENTRY_CLASS: &str = "java/util/Map$Entry";
+const JAVA_LANG_CHAR_SEQUENCE_CLASS: &str = "java/lang/CharSequence";
+const JAVA_LANG_REFLECT_METHOD_CLASS: &str = "java/lang/reflect/Method";
+const JAVA_LANG_ERROR_CLASS: &str = "java/lang/Error";
+const JAVA_LANG_RUNTIME_EXCEPTION_CLASS: &str = "java/lang/RuntimeException";
+const JAVA_LANG_ILLEGAL_ARGUMENT_EXCEPTION_CLASS: &str = "java/lang/IllegalArgumentException";
+const JAVA_LANG_ILLEGAL_STATE_EXCEPTION_CLASS: &str = "java/lang/IllegalStateException";
+const JAVA_UTIL_NO_SUCH_ELEMENT_EXCEPTION_CLASS: &str = "java/util/NoSuchElementException";
+const JAVA_LANG_INDEX_OUT_OF_BOUNDS_EXCEPTION_CLASS: &str = "java/lang/IndexOutOfBoundsException";
 const SERVICE_RUNTIME_ADAPTER_CLASS: &str = "com/exonum/binding/core/runtime/ServiceRuntimeAdapter";
+/// Name of the environment variable overriding the name of `ServiceRuntimeAdapter` class,
+/// e.g., when the Java classes are relocated to another package.
+const ADAPTER_CLASS_ENV: &str = "EJB_ADAPTER_CLASS";
+/// Name of the environment variable listing (comma-separated) the names of the required
+/// `ServiceRuntimeAdapter` and `BlockchainData` methods, see `RequiredMethods`.
+const REQUIRED_METHODS_ENV: &str = "EJB_REQUIRED_METHODS";
+/// The maximum time `reset_cache` waits for the adapter calls in progress.
+const RESET_TIMEOUT: Duration = Duration::from_secs(5);
+/// The interval of checking whether the adapter calls are completed.
+const RESET_POLL_INTERVAL: Duration = Duration::from_millis(1);
+const BLOCKCHAIN_DATA_CLASS: &str = "com/exonum/binding/core/blockchain/BlockchainData";
+const ABSTRACT_ACCESS_CLASS: &str = "com/exonum/binding/core/storage/database/AbstractAccess";
+const BLOCKCHAIN_CLASS: &str = "com/exonum/binding/core/blockchain/Blockchain";
+const BLOCK_CLASS: &str = "com/exonum/binding/core/blockchain/Block";
+const HASH_CODE_CLASS: &str = "com/exonum/binding/common/hash/HashCode";
+const EXECUTION_EXCEPTION_CLASS: &str = "com/exonum/binding/core/service/ExecutionException";
+const UNEXPECTED_EXECUTION_EXCEPTION_CLASS: &str =
+    "com/exonum/binding/core/runtime/UnexpectedExecutionException";
+const KEY_PAIR_CLASS: &str = "com/exonum/binding/common/crypto/KeyPair";
+/// Description of the class loader that cannot be described.
+const UNKNOWN_CLASS_LOADER: &str = "<unknown>";
 
-static INIT: Once = Once::new();
+/// Serializes initialization and release of the cache.
+static INIT_LOCK: Mutex<()> = const_mutex(());
+/// Whether the cache is initialized. Is set only while `INIT_LOCK` is held.
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+/// Whether a class loader mismatch was detected, see `loader_mismatch_detected`.
+static LOADER_MISMATCH: AtomicBool = AtomicBool::new(false);
+/// The JVM the cache is initialized against, see `java_vm`.
+static JAVA_VM: AtomicPtr<JavaVM> = AtomicPtr::new(ptr::null_mut());
+/// The number of adapter calls in progress, see `adapter_call_guard`.
+static IN_FLIGHT_CALLS: AtomicUsize = AtomicUsize::new(0);
+/// The number of `reset_cache` calls in progress, which reject new adapter calls.
+static RESETS_IN_PROGRESS: AtomicUsize = AtomicUsize::new(0);
+/// Whether `JNI_OnLoad` aborts the process on cache failure, see `set_on_cache_failure`.
+static ABORT_ON_CACHE_FAILURE: AtomicBool = AtomicBool::new(false);
+/// The number of times the cache was initialized, see `cache_generation`.
+static CACHE_GENERATION: AtomicUsize = AtomicUsize::new(0);
+/// The duration of the last successful caching, see `last_cache_duration`.
+static LAST_CACHE_DURATION: Mutex<Option<Duration>> = const_mutex(None);
+/// The callbacks run once the cache is initialized, see `on_cache_ready`.
+static CACHE_READY_CALLBACKS: Mutex<Vec<fn(&JNIEnv)>> = const_mutex(Vec::new());
+/// The name of `ServiceRuntimeAdapter` class the cache is initialized with, see `adapter_class`.
+static ADAPTER_CLASS: Mutex<String> = const_mutex(String::new());
+/// Interned Java strings, see `strings::intern_string`.
+static INTERNED_STRINGS: Mutex<Vec<(&'static str, CountedRef)>> = const_mutex(Vec::new());
+/// Descriptions of the cached entries, see `cached_entries`.
+static CACHED_ENTRIES: Mutex<Vec<CachedEntry>> = const_mutex(Vec::new());
 
 static mut OBJECT_GET_CLASS: Option<JMethodID> = None;
+static mut OBJECT_TO_STRING: Option<JMethodID> = None;
+static mut THREAD_GET_CONTEXT_CLASS_LOADER: Option<JMethodID> = None;
+static mut AUTO_CLOSEABLE_CLOSE: Option<JMethodID> = None;
 static mut CLASS_GET_NAME: Option<JMethodID> = None;
+static mut CLASS_GET_SIMPLE_NAME: Option<JMethodID> = None;
+static mut CLASS_IS_ASSIGNABLE_FROM: Option<JMethodID> = None;
 static mut THROWABLE_GET_MESSAGE: Option<JMethodID> = None;
+static mut THROWABLE_GET_LOCALIZED_MESSAGE: Option<JMethodID> = None;
 static mut THROWABLE_GET_CAUSE: Option<JMethodID> = None;
+static mut THROWABLE_GET_STACK_TRACE: Option<JMethodID> = None;
+static mut STACK_TRACE_ELEMENT_TO_STRING: Option<JMethodID> = None;
+static mut THROWABLE_ADD_SUPPRESSED: Option<JMethodID> = None;
 static mut EXECUTION_EXCEPTION_GET_ERROR_CODE: Option<JMethodID> = None;
+static mut EXECUTION_EXCEPTION_CONSTRUCTOR: Option<JMethodID> = None;
+static mut UNEXPECTED_EXECUTION_EXCEPTION_CONSTRUCTOR: Option<JMethodID> = None;
+static mut INTEGER_INT_VALUE: Option<JMethodID> = None;
+static mut LONG_LONG_VALUE: Option<JMethodID> = None;
+static mut MESSAGE_LITE_TO_BYTE_ARRAY: Option<JMethodID> = None;
+static mut ARRAY_LIST_CONSTRUCTOR: Option<JMethodID> = None;
+static mut LIST_ADD: Option<JMethodID> = None;
+static mut LIST_SIZE: Option<JMethodID> = None;
+static mut MAP_ENTRY_GET_KEY: Option<JMethodID> = None;
+static mut MAP_ENTRY_GET_VALUE: Option<JMethodID> = None;
+static mut CHAR_SEQUENCE_LENGTH: Option<JMethodID> = None;
+static mut CHAR_SEQUENCE_TO_STRING: Option<JMethodID> = None;
+static mut METHOD_GET_NAME: Option<JMethodID> = None;
+static mut METHOD_GET_PARAMETER_TYPES: Option<JMethodID> = None;
+
+static mut CLASS_FOR_NAME: Option<JStaticMethodID> = None;
+static mut THREAD_CURRENT_THREAD: Option<JStaticMethodID> = None;
+static mut KEY_PAIR_NEW_INSTANCE: Option<JStaticMethodID> = None;
+static mut SYSTEM_NANO_TIME: Option<JStaticMethodID> = None;
+static mut INTEGER_VALUE_OF: Option<JStaticMethodID> = None;
+static mut LONG_VALUE_OF: Option<JStaticMethodID> = None;
+
+static mut EXECUTION_EXCEPTION_ERROR_CODE: Option<JFieldID> = None;
+
+static mut JAVA_LANG_CLASS: Option<CountedRef> = None;
+static mut JAVA_LANG_ERROR: Option<CountedRef> = None;
+static mut JAVA_LANG_SYSTEM: Option<CountedRef> = None;
+static mut JAVA_LANG_INTEGER: Option<CountedRef> = None;
+static mut JAVA_LANG_LONG: Option<CountedRef> = None;
+static mut JAVA_UTIL_ARRAY_LIST: Option<CountedRef> = None;
+static mut JAVA_LANG_RUNTIME_EXCEPTION: Option<CountedRef> = None;
+static mut JAVA_LANG_ILLEGAL_ARGUMENT_EXCEPTION: Option<CountedRef> = None;
+static mut JAVA_LANG_ILLEGAL_STATE_EXCEPTION: Option<CountedRef> = None;
+static mut JAVA_UTIL_NO_SUCH_ELEMENT_EXCEPTION: Option<CountedRef> = None;
+static mut JAVA_LANG_INDEX_OUT_OF_BOUNDS_EXCEPTION: Option<CountedRef> = None;
+static mut EXECUTION_EXCEPTION: Option<CountedRef> = None;
+static mut UNEXPECTED_EXECUTION_EXCEPTION: Option<CountedRef> = None;
+static mut KEY_PAIR: Option<CountedRef> = None;
+static mut BOOLEAN_TRUE: Option<CountedRef> = None;
+static mut BOOLEAN_FALSE: Option<CountedRef> = None;
+/// The cached exception classes by their JNI names, see `classes_refs::all`.
+static mut EXCEPTION_CLASSES: Vec<(&str, GlobalRef)> = Vec::new();
+static mut SERVICE_RUNTIME_ADAPTER: Option<CountedRef> = None;
+
+/// Cached `JMethodID`, which is set once per initialization of the cache and reset
+/// on its release.
+struct CachedMethod(AtomicPtr<_jmethodID>);
+
+impl CachedMethod {
+    /// Creates an empty cell.
+    const fn new() -> Self {
+        CachedMethod(AtomicPtr::new(ptr::null_mut()))
+    }
+
+    /// Returns the method id, if it is set.
+    fn get(&self) -> Option<JMethodID<'static>> {
+        let raw = self.0.load(Ordering::Acquire);
+        if raw.is_null() {
+            None
+        } else {
+            Some(raw.into())
+        }
+    }
+
+    /// Sets the method id.
+    ///
+    /// Panics if the method id is already set.
+    fn set(&self, method_id: JMethodID<'static>) {
+        debug_assert!(
+            !method_id.into_inner().is_null(),
+            "Cached method id is null"
+        );
+        let result = self.0.compare_exchange(
+            ptr::null_mut(),
+            method_id.into_inner(),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+        assert!(result.is_ok(), "Method id is already cached");
+    }
+
+    /// Sets the method id unless it is already set, e.g., resolved concurrently
+    /// by another thread.
+    fn set_if_empty(&self, method_id: JMethodID<'static>) {
+        let _ = self.0.compare_exchange(
+            ptr::null_mut(),
+            method_id.into_inner(),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+    }
+
+    /// Resets the method id.
+    fn clear(&self) {
+        self.0.store(ptr::null_mut(), Ordering::Release);
+    }
+}
+
+/// Declares cached instance methods: for each entry it generates a static holding
+/// the method id, a public accessor returning it and a fallible `try_` accessor.
+/// Generates `cache` and `clear` functions, which must be called from `cache_methods`
+/// and `clear_methods` correspondingly, so every declared method is cached (or, if not
+/// required, see `RequiredMethods`, tried to be cached). Can be used once per module.
+///
+/// ```ignore
+/// cache_methods! {
+///     /// Accessor documentation.
+///     STATIC_NAME: (CLASS_NAME, "javaMethodName", "()V") => accessor_name, try_accessor_name;
+/// }
+/// ```
+macro_rules! cache_methods {
+    (
+        $(
+            $(#[$attr:meta])*
+            $static_name:ident: ($class:expr, $name:expr, $sig:expr $(,)?)
+                => $accessor:ident, $try_accessor:ident;
+        )*
+    ) => {
+        $(
+            static $static_name: CachedMethod = CachedMethod::new();
+        )*
+
+        $(
+            $(#[$attr])*
+            ///
+            /// Panics if the cache is not initialized or the method is not cached.
+            pub fn $accessor() -> JMethodID<'static> {
+                $try_accessor().unwrap_or_else(|e| panic!("{}", e))
+            }
+
+            $(#[$attr])*
+            ///
+            /// Returns an error if the cache is not initialized or the method is not cached.
+            pub fn $try_accessor() -> Result<JMethodID<'static>, CacheError> {
+                if !is_cache_initialized() {
+                    return Err(CacheError::NotInitialized);
+                }
+                $static_name.get().ok_or_else(|| CacheError::MethodNotFound {
+                    class: $class.to_owned(),
+                    name: $name.to_owned(),
+                    sig: $sig.to_owned(),
+                })
+            }
+        )*
+
+        /// Resolves the method ids declared in this module in the order of declaration,
+        /// skipping the missing methods that are not required. The ids are cached
+        /// by `commit`.
+        pub(super) fn lookup(
+            env: &JNIEnv,
+            required: &RequiredMethods,
+        ) -> Result<Vec<Option<JMethodID<'static>>>, CacheError> {
+            Ok(vec![$(
+                match get_method_id(env, $class, $name, $sig) {
+                    Ok(method_id) => Some(method_id),
+                    Err(e) if !required.is_required($name) => {
+                        warn!("Skipped caching of the optional method: {}", e);
+                        None
+                    }
+                    Err(e) => return Err(e),
+                },
+            )*])
+        }
+
+        /// Caches the method ids resolved by `lookup`.
+        pub(super) fn commit(method_ids: Vec<Option<JMethodID<'static>>>) {
+            let mut method_ids = method_ids.into_iter();
+            $(
+                if let Some(method_id) = method_ids.next().unwrap() {
+                    $static_name.set(method_id);
+                }
+            )*
+        }
+
+        /// Returns `true` if none of the method ids declared in this module is cached.
+        pub(super) fn is_clear() -> bool {
+            let cached: &[bool] = &[$($static_name.get().is_some()),*];
+            !cached.contains(&true)
+        }
+
+        /// Resets the method ids declared in this module.
+        pub(super) fn clear() {
+            $(
+                $static_name.clear();
+            )*
+        }
+
+        /// Sets the method ids declared in this module to the given sentinel.
+        #[cfg(feature = "mock-cache")]
+        pub(super) fn mock(method_id: JMethodID<'static>) {
+            $(
+                $static_name.set_if_empty(method_id);
+            )*
+        }
+
+        /// Returns the names and signatures of the methods declared in this module.
+        // Not every module verifies the declared methods, see `verify_adapter_contract`.
+        #[allow(dead_code)]
+        pub(super) fn declared_methods() -> Vec<(&'static str, &'static str)> {
+            vec![$(($name, $sig)),*]
+        }
+    };
+}
+
+/// Declares lazily cached instance methods: unlike `cache_methods`, the method ids
+/// are resolved on the first access to the accessor, using the cached class reference,
+/// and memoized until the cache is released. Generates `clear_lazy` function, which must
+/// be called from `clear_methods`. Can be used once per module.
+///
+/// Lazy caching shortens `JNI_OnLoad` and allows the Java side to omit rarely used
+/// methods, but moves the fail-fast check of the method presence from the load time
+/// to the first call. Hence, it shall be used only for the methods that are not
+/// required by every deployment.
+///
+/// ```ignore
+/// lazy_cache_methods! {
+///     /// Accessor documentation.
+///     STATIC_NAME: (classes_refs::class_ref, CLASS_NAME, "javaMethodName", "()V")
+///         => accessor_name, try_accessor_name;
+/// }
+/// ```
+macro_rules! lazy_cache_methods {
+    (
+        $(
+            $(#[$attr:meta])*
+            $static_name:ident: ($class_ref:expr, $class:expr, $name:expr, $sig:expr $(,)?)
+                => $accessor:ident, $try_accessor:ident;
+        )*
+    ) => {
+        $(
+            static $static_name: CachedMethod = CachedMethod::new();
+        )*
+
+        $(
+            $(#[$attr])*
+            ///
+            /// The method id is resolved on the first access. Panics if the cache is not
+            /// initialized or the method is not found.
+            pub fn $accessor() -> JMethodID<'static> {
+                $try_accessor().unwrap_or_else(|e| panic!("{}", e))
+            }
+
+            $(#[$attr])*
+            ///
+            /// The method id is resolved on the first access. Returns an error if the cache
+            /// is not initialized or the method is not found.
+            pub fn $try_accessor() -> Result<JMethodID<'static>, CacheError> {
+                if !is_cache_initialized() {
+                    return Err(CacheError::NotInitialized);
+                }
+                if let Some(method_id) = $static_name.get() {
+                    return Ok(method_id);
+                }
+                let vm = try_java_vm().ok_or(CacheError::NotInitialized)?;
+                let method_id = with_attached_vm(vm, |env| {
+                    get_method_id_lazily(env, &$class_ref(), $class, $name, $sig)
+                })?;
+                $static_name.set_if_empty(method_id);
+                Ok(method_id)
+            }
+        )*
+
+        /// Resets the lazily resolved method ids declared in this module.
+        pub(super) fn clear_lazy() {
+            $(
+                $static_name.clear();
+            )*
+        }
 
-static mut RUNTIME_ADAPTER_INITIALIZE: Option<JMethodID> = None;
-static mut RUNTIME_ADAPTER_DEPLOY_ARTIFACT: Option<JMethodID> = None;
-static mut RUNTIME_ADAPTER_IS_ARTIFACT_DEPLOYED: Option<JMethodID> = None;
-static mut RUNTIME_ADAPTER_INITIATE_ADDING_SERVICE: Option<JMethodID> = None;
-static mut RUNTIME_ADAPTER_INITIATE_RESUMING_SERICE: Option<JMethodID> = None;
-static mut RUNTIME_ADAPTER_UPDATE_SERVICE_STATUS: Option<JMethodID> = None;
-static mut RUNTIME_ADAPTER_EXECUTE_TX: Option<JMethodID> = None;
-static mut RUNTIME_ADAPTER_BEFORE_TRANSACTIONS: Option<JMethodID> = None;
-static mut RUNTIME_ADAPTER_AFTER_TRANSACTIONS: Option<JMethodID> = None;
-static mut RUNTIME_ADAPTER_AFTER_COMMIT: Option<JMethodID> = None;
-static mut RUNTIME_ADAPTER_SHUTDOWN: Option<JMethodID> = None;
-
-static mut JAVA_LANG_ERROR: Option<GlobalRef> = None;
-static mut JAVA_LANG_RUNTIME_EXCEPTION: Option<GlobalRef> = None;
-static mut JAVA_LANG_ILLEGAL_ARGUMENT_EXCEPTION: Option<GlobalRef> = None;
-static mut EXECUTION_EXCEPTION: Option<GlobalRef> = None;
-static mut UNEXPECTED_EXECUTION_EXCEPTION: Option<GlobalRef> = None;
+        /// Sets the lazily resolved method ids declared in this module to the given sentinel.
+        #[cfg(feature = "mock-cache")]
+        pub(super) fn mock_lazy(method_id: JMethodID<'static>) {
+            $(
+                $static_name.set_if_empty(method_id);
+            )*
+        }
+
+        /// Returns the names and signatures of the lazily resolved methods declared
+        /// in this module.
+        pub(super) fn declared_lazy_methods() -> Vec<(&'static str, &'static str)> {
+            vec![$(($name, $sig)),*]
+        }
+    };
+}
 
 /// This function is executed on loading native library by JVM.
 /// It initializes the cache of method and class references.
+///
+/// Returns the JNI version reported by the JVM capped at the highest version the binding
+/// supports: JNI 1.8 unless another one is requested with `EJB_JNI_VERSION`
+/// environment variable and supported by the JVM. The name of `ServiceRuntimeAdapter` class
+/// can be overridden with `EJB_ADAPTER_CLASS` environment variable, and the required methods
+/// can be listed in `EJB_REQUIRED_METHODS` (see `RequiredMethods`).
+///
+/// If the cache cannot be initialized, acts according to the `FailurePolicy`
+/// set with `set_on_cache_failure`.
 #[allow(non_snake_case)]
 #[no_mangle]
 pub extern "system" fn JNI_OnLoad(vm: JavaVM, _: *mut c_void) -> jint {
-    let env = vm.get_env().expect("Cannot get reference to the JNIEnv");
+    // `get_env` requests JNI 1.8 environment
+    let env = match vm.get_env() {
+        Ok(env) => env,
+        Err(e) => {
+            error!("JVM does not support JNI 1.8: {}", e);
+            return INVALID_JNI_VERSION;
+        }
+    };
+    let version = negotiate_jni_version(&env, requested_jni_version(&vm));
+    debug!("Using JNI version {}", format_jni_version(version));
+
+    let (result, backtrace) = catch_unwind_with_backtrace(|| match try_init_cache(&env) {
+        Ok(()) => version,
+        Err(e) => {
+            error!(
+                "Failed to initialize JNI cache, check that the native library matches \
+                 the Java classes: {}",
+                e
+            );
+            INVALID_JNI_VERSION
+        }
+    });
+    let version = result.unwrap_or_else(|payload| on_load_panic(payload, backtrace));
+    if version == INVALID_JNI_VERSION && on_cache_failure() == FailurePolicy::AbortProcess {
+        error!("Aborting the process as JNI cache is not initialized");
+        std::process::abort();
+    }
+    version
+}
+
+/// Behaviour of `JNI_OnLoad` if the cache cannot be initialized.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FailurePolicy {
+    /// Return the invalid JNI version, so the JVM fails to load the native library
+    /// with `UnsatisfiedLinkError` that can be handled by the Java code. The default.
+    ReturnInvalidVersion,
+    /// Abort the process (regardless of the `panic` strategy of the build).
+    AbortProcess,
+}
+
+impl Default for FailurePolicy {
+    fn default() -> Self {
+        FailurePolicy::ReturnInvalidVersion
+    }
+}
+
+/// Sets the behaviour of `JNI_OnLoad` if the cache cannot be initialized. Must be called
+/// before the native library is loaded by the JVM to take effect.
+pub fn set_on_cache_failure(policy: FailurePolicy) {
+    let abort = policy == FailurePolicy::AbortProcess;
+    ABORT_ON_CACHE_FAILURE.store(abort, Ordering::Release);
+}
+
+/// Returns the behaviour of `JNI_OnLoad` if the cache cannot be initialized.
+pub fn on_cache_failure() -> FailurePolicy {
+    if ABORT_ON_CACHE_FAILURE.load(Ordering::Acquire) {
+        FailurePolicy::AbortProcess
+    } else {
+        FailurePolicy::ReturnInvalidVersion
+    }
+}
+
+/// Logs the payload (and the backtrace, if recorded) of the panic occurred in `JNI_OnLoad`,
+/// so the cause of failure is not lost, and returns the invalid JNI version.
+fn on_load_panic(payload: Box<dyn Any + Send>, backtrace: Option<String>) -> jint {
+    match backtrace {
+        Some(backtrace) => error!(
+            "Panic while initializing JNI cache: {}\n{}",
+            any_to_string(&payload),
+            backtrace
+        ),
+        None => error!(
+            "Panic while initializing JNI cache: {}",
+            any_to_string(&payload)
+        ),
+    }
+    INVALID_JNI_VERSION
+}
+
+/// Same as `catch_unwind`, but also returns the backtrace of the panic if `RUST_BACKTRACE`
+/// is set.
+///
+/// The backtrace is recorded by a panic hook installed for the duration of the call;
+/// the previous hook is restored afterwards. The hook records only the panics of
+/// the current thread, delegating the panics of other threads to the previous hook.
+fn catch_unwind_with_backtrace<F, R>(f: F) -> (std::thread::Result<R>, Option<String>)
+where
+    F: FnOnce() -> R + UnwindSafe,
+{
+    thread_local! {
+        static RECORD_BACKTRACE: Cell<bool> = Cell::new(false);
+        static BACKTRACE: RefCell<Option<String>> = RefCell::new(None);
+    }
+
+    let record_backtrace = env::var_os("RUST_BACKTRACE").map_or(false, |value| value != "0");
+    if !record_backtrace {
+        return (catch_unwind(f), None);
+    }
+
+    let previous_hook = Arc::new(panic::take_hook());
+    let hook_previous = Arc::clone(&previous_hook);
+    panic::set_hook(Box::new(move |info| {
+        if RECORD_BACKTRACE.with(Cell::get) {
+            let backtrace = format!("{:?}", Backtrace::new());
+            BACKTRACE.with(|recorded| *recorded.borrow_mut() = Some(backtrace));
+        } else {
+            (**hook_previous)(info)
+        }
+    }));
+    let _restore_hook = RestoreHookGuard(Some(previous_hook));
+
+    RECORD_BACKTRACE.with(|record| record.set(true));
+    let result = catch_unwind(f);
+    RECORD_BACKTRACE.with(|record| record.set(false));
+
+    (
+        result,
+        BACKTRACE.with(|recorded| recorded.borrow_mut().take()),
+    )
+}
+
+/// Panic hook as returned by `panic::take_hook`.
+type PanicHook = Box<dyn Fn(&panic::PanicInfo) + Sync + Send + 'static>;
+
+/// Restores the given panic hook when dropped, replacing the hook installed
+/// by `catch_unwind_with_backtrace`.
+struct RestoreHookGuard(Option<Arc<PanicHook>>);
+
+impl Drop for RestoreHookGuard {
+    fn drop(&mut self) {
+        // Drop the installed hook, releasing its reference to the previous one.
+        drop(panic::take_hook());
+        if let Some(previous_hook) = self.0.take() {
+            // The installed hook may still be running in another thread, in which case
+            // the previous hook is shared with it and installed behind a forwarding hook.
+            let previous_hook = Arc::try_unwrap(previous_hook)
+                .unwrap_or_else(|shared| Box::new(move |info: &panic::PanicInfo| (**shared)(info)));
+            panic::set_hook(previous_hook);
+        }
+    }
+}
+
+/// Returns the JNI version requested with `EJB_JNI_VERSION` environment variable.
+///
+/// Falls back to JNI 1.8 if the variable is not set, has an unknown value or the requested
+/// version is not supported by the JVM.
+fn requested_jni_version(vm: &JavaVM) -> jint {
+    let requested = match env::var(JNI_VERSION_ENV) {
+        Ok(requested) => requested,
+        Err(_) => return JNI_VERSION_1_8,
+    };
+    match parse_jni_version(&requested) {
+        Some(version) if is_jni_version_supported(vm, version) => version,
+        Some(_) => {
+            debug!(
+                "JNI version {} is not supported by the JVM, falling back to 1.8",
+                requested
+            );
+            JNI_VERSION_1_8
+        }
+        None => {
+            debug!(
+                "Unknown JNI version {:?} in {}, falling back to 1.8",
+                requested, JNI_VERSION_ENV
+            );
+            JNI_VERSION_1_8
+        }
+    }
+}
+
+/// Returns the name of `ServiceRuntimeAdapter` class requested with `EJB_ADAPTER_CLASS`
+/// environment variable, or the default one if the variable is not set.
+fn requested_adapter_class() -> String {
+    match env::var(ADAPTER_CLASS_ENV) {
+        Ok(class) if !class.trim().is_empty() => {
+            let class = to_internal_class_name(&class);
+            debug!("Using {} as ServiceRuntimeAdapter class", class);
+            class
+        }
+        _ => SERVICE_RUNTIME_ADAPTER_CLASS.to_owned(),
+    }
+}
+
+/// Converts the binary class name (`com.example.Foo`) to the internal form
+/// (`com/example/Foo`) expected by JNI. Names in the internal form are returned as is.
+fn to_internal_class_name(class: &str) -> String {
+    class.trim().replace('.', "/")
+}
+
+/// Returns the name of `ServiceRuntimeAdapter` class the cache is initialized with,
+/// or the default one if the cache is not initialized.
+fn adapter_class() -> String {
+    let class = ADAPTER_CLASS.lock();
+    if class.is_empty() {
+        SERVICE_RUNTIME_ADAPTER_CLASS.to_owned()
+    } else {
+        class.clone()
+    }
+}
+
+/// Returns the JNI version of the JVM (as reported by `GetVersion`), capped at the given
+/// highest version supported by the binding.
+fn negotiate_jni_version(env: &JNIEnv, max_version: jint) -> jint {
+    match env.get_version() {
+        Ok(version) => cap_jni_version(version.into(), max_version),
+        Err(_) => max_version,
+    }
+}
+
+/// Caps the JNI version of the JVM at the highest version supported by the binding.
+fn cap_jni_version(jvm_version: jint, max_version: jint) -> jint {
+    cmp::min(jvm_version, max_version)
+}
+
+/// Formats the JNI version as `major.minor`, omitting the zero minor version
+/// of Java 9 and later (e.g., `1.8`, `10`).
+fn format_jni_version(version: jint) -> String {
+    let (major, minor) = (version >> 16, version & 0xffff);
+    if minor == 0 {
+        major.to_string()
+    } else {
+        format!("{}.{}", major, minor)
+    }
+}
+
+/// Parses the JNI version in the format of `EJB_JNI_VERSION` environment variable.
+fn parse_jni_version(version: &str) -> Option<jint> {
+    match version.trim() {
+        "1.8" => Some(JNI_VERSION_1_8),
+        "9" => Some(JNI_VERSION_9),
+        "10" => Some(JNI_VERSION_10),
+        _ => None,
+    }
+}
+
+/// Checks if the JVM supports the given JNI version.
+fn is_jni_version_supported(vm: &JavaVM, version: jint) -> bool {
+    let vm = vm.get_java_vm_pointer();
+    let mut env = ptr::null_mut();
+    unsafe {
+        match (**vm).GetEnv {
+            Some(get_env) => get_env(vm, &mut env, version) == JNI_OK,
+            None => false,
+        }
+    }
+}
+
+/// This function is executed when the class loader containing the native library
+/// is garbage collected. It releases the cached references, so that a subsequent
+/// `JNI_OnLoad` can initialize the cache again.
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "system" fn JNI_OnUnload(_: JavaVM, _: *mut c_void) {
+    let _ = catch_unwind(reset_cache);
+}
+
+/// Verifies the JNI cache and returns a report of the check, see `self_test`.
+///
+/// Returns `null` with a pending exception if the report cannot be created.
+#[no_mangle]
+pub extern "system" fn Java_com_exonum_binding_core_runtime_ServiceRuntimeAdapter_nativeSelfTest(
+    env: JNIEnv,
+    _: JClass,
+) -> jstring {
+    let res = panic::catch_unwind(|| {
+        let report = self_test(&env);
+        env.new_string(report).map(JString::into_inner)
+    });
+    unwrap_exc_or(&env, res, ptr::null_mut())
+}
+
+/// An error occurred during caching of Java classes and methods. Usually signifies
+/// that the loaded Java classes do not match the native library.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CacheError {
+    /// The cache is not initialized.
+    NotInitialized,
+    /// The class is not found.
+    ClassNotFound {
+        /// JNI name of the class.
+        class: String,
+        /// Description of the class loader that was searched.
+        loader: String,
+    },
+    /// The method with the given signature is not found in the class.
+    MethodNotFound {
+        /// JNI name of the class.
+        class: String,
+        /// Name of the method.
+        name: String,
+        /// JNI signature of the method.
+        sig: String,
+    },
+    /// The field with the given type signature is not found in the class.
+    FieldNotFound {
+        /// JNI name of the class.
+        class: String,
+        /// Name of the field.
+        name: String,
+        /// JNI type signature of the field.
+        sig: String,
+    },
+    /// JNI error occurred during caching, e.g., a global reference cannot be created.
+    Jni(String),
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CacheError::NotInitialized => write!(f, "JNI cache is not initialized"),
+            CacheError::ClassNotFound { class, loader } => {
+                write!(f, "Class {} not found by class loader {}", class, loader)
+            }
+            CacheError::MethodNotFound { class, name, sig } => write!(
+                f,
+                "Method {} with signature {} of class {} not found",
+                name, sig, class
+            ),
+            CacheError::FieldNotFound { class, name, sig } => write!(
+                f,
+                "Field {} with signature {} of class {} not found",
+                name, sig, class
+            ),
+            CacheError::Jni(e) => write!(f, "JNI error while caching: {}", e),
+        }
+    }
+}
+
+impl Error for CacheError {}
+
+impl From<CacheError> for JniError {
+    fn from(e: CacheError) -> Self {
+        JniErrorKind::Msg(e.to_string()).into()
+    }
+}
+
+impl From<JniError> for CacheError {
+    fn from(e: JniError) -> Self {
+        CacheError::Jni(e.to_string())
+    }
+}
+
+/// Mismatch between the `ServiceRuntimeAdapter` class and the methods expected by
+/// the native library, see `verify_adapter_contract`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractMismatch {
+    /// JNI name of the adapter class.
+    pub class: String,
+    /// Whether the class itself is not found; all methods are missing in this case.
+    pub class_not_found: bool,
+    /// Expected methods absent in the class, as `name` and JNI signature.
+    pub missing: Vec<(String, String)>,
+    /// Expected methods present in the class with other signatures only,
+    /// as `name` and the expected JNI signature.
+    pub mismatched: Vec<(String, String)>,
+}
+
+impl fmt::Display for ContractMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn list(methods: &[(String, String)]) -> String {
+            let methods: Vec<_> = methods
+                .iter()
+                .map(|(name, sig)| format!("{}{}", name, sig))
+                .collect();
+            methods.join(", ")
+        }
+
+        write!(
+            f,
+            "Class {} does not match the native library, check that the versions \
+             of the Java classes and the native library are the same",
+            self.class
+        )?;
+        if self.class_not_found {
+            return write!(f, ": the class is not found");
+        }
+        if !self.missing.is_empty() {
+            write!(f, "; missing methods: {}", list(&self.missing))?;
+        }
+        if !self.mismatched.is_empty() {
+            write!(
+                f,
+                "; methods with unexpected signatures: {}",
+                list(&self.mismatched)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ContractMismatch {}
+
+/// Checks that the `ServiceRuntimeAdapter` class visible in the given environment declares
+/// all the methods used by the native library with the expected signatures (including
+/// the optional and lazily resolved ones). Does not require the cache to be initialized.
+///
+/// Unlike the caching, which fails on the first missing method, returns the complete list
+/// of the mismatches, so it is suitable for the preflight checks of the deployment.
+pub fn verify_adapter_contract(env: &JNIEnv) -> Result<(), ContractMismatch> {
+    let class = if is_cache_initialized() {
+        adapter_class()
+    } else {
+        requested_adapter_class()
+    };
+    let mut mismatch = ContractMismatch {
+        class: class.clone(),
+        class_not_found: false,
+        missing: Vec::new(),
+        mismatched: Vec::new(),
+    };
+    let mut methods = runtime_adapter::declared_methods();
+    methods.extend(runtime_adapter::declared_lazy_methods());
+
+    let class_object = match env.find_class(&class) {
+        Ok(class_object) => class_object,
+        Err(_) => {
+            let _ = env.exception_clear();
+            mismatch.class_not_found = true;
+            mismatch.missing = methods
+                .into_iter()
+                .map(|(name, sig)| (name.to_owned(), sig.to_owned()))
+                .collect();
+            return Err(mismatch);
+        }
+    };
+    let mut declared_names: Option<Vec<String>> = None;
+    for (name, sig) in methods {
+        if env.get_method_id(class_object, name, sig).is_ok() {
+            continue;
+        }
+        let _ = env.exception_clear();
+        let names = declared_names.get_or_insert_with(|| declared_method_names(env, class_object));
+        let method = (name.to_owned(), sig.to_owned());
+        if names.iter().any(|declared| declared == name) {
+            mismatch.mismatched.push(method);
+        } else {
+            mismatch.missing.push(method);
+        }
+    }
+    let _ = env.delete_local_ref(class_object.into());
+
+    if mismatch.missing.is_empty() && mismatch.mismatched.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatch)
+    }
+}
+
+/// Returns the names of the methods declared by the class using reflection, or an empty
+/// list if they cannot be obtained (the exception is cleared).
+fn declared_method_names(env: &JNIEnv, class: JClass) -> Vec<String> {
+    let names = || -> JniResult<Vec<String>> {
+        let methods = env
+            .call_method(
+                class,
+                "getDeclaredMethods",
+                "()[Ljava/lang/reflect/Method;",
+                &[],
+            )?
+            .l()?
+            .into_inner();
+        let len = env.get_array_length(methods)?;
+        let mut names = Vec::with_capacity(len as usize);
+        for i in 0..len {
+            let method = env.get_object_array_element(methods, i)?;
+            let name = env
+                .call_method(method, "getName", "()Ljava/lang/String;", &[])?
+                .l()?;
+            names.push(convert_to_string(env, name)?);
+            env.delete_local_ref(name)?;
+            env.delete_local_ref(method)?;
+        }
+        Ok(names)
+    }();
+    names.unwrap_or_else(|_| {
+        let _ = env.exception_clear();
+        Vec::new()
+    })
+}
+
+/// Description of a cached entry.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CachedEntry {
+    /// JNI name of the class.
+    pub class: String,
+    /// Name of the method or field; empty for classes.
+    pub name: String,
+    /// JNI signature of the method or field; empty for classes.
+    pub sig: String,
+    /// Kind of the entry.
+    pub kind: CachedEntryKind,
+}
+
+/// Kind of a cached entry.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CachedEntryKind {
+    /// An instance method (or a constructor).
+    Method,
+    /// A static method.
+    StaticMethod,
+    /// An instance field.
+    Field,
+    /// A class reference.
+    Class,
+    /// A reference to the value of a static field.
+    StaticField,
+}
+
+/// Initializes JNI cache considering synchronization
+///
+/// Panics if any of the cached classes or methods is not found.
+pub fn init_cache(env: &JNIEnv) {
+    try_init_cache(env).unwrap_or_else(|e| panic!("{}", e));
+}
+
+/// Initializes JNI cache considering synchronization
+///
+/// Returns an error if any of the cached classes or methods is not found; the cache
+/// remains uninitialized in this case.
+///
+/// If the cache is already initialized, checks that the cached classes are the ones
+/// visible in the given environment, see `loader_mismatch_detected`.
+///
+/// A re-entrant call from the same thread while the cache is being initialized (e.g.,
+/// from a static initializer of a cached class calling the native code) is skipped
+/// with a warning, as waiting for the initialization would deadlock.
+pub fn try_init_cache(env: &JNIEnv) -> Result<(), CacheError> {
+    if CACHING_ON_THIS_THREAD.with(Cell::get) {
+        warn!("Skipped re-entrant initialization of JNI cache from the thread initializing it");
+        return Ok(());
+    }
+    let lock = INIT_LOCK.lock();
+    if INITIALIZED.load(Ordering::Acquire) {
+        detect_loader_mismatch(env);
+    } else {
+        let start = Instant::now();
+        {
+            let _guard = CachingGuard::new();
+            unsafe {
+                cache_methods(env).map_err(|e| {
+                    clear_methods();
+                    e
+                })?
+            };
+        }
+        let duration = start.elapsed();
+        let generation = CACHE_GENERATION.fetch_add(1, Ordering::AcqRel) + 1;
+        let load_kind = if generation == 1 { "cold" } else { "reload" };
+        debug!(
+            "Caching took {:?} ({}, generation {})",
+            duration, load_kind, generation
+        );
+        *LAST_CACHE_DURATION.lock() = Some(duration);
+        cache_java_vm(env);
+        INITIALIZED.store(true, Ordering::Release);
+
+        // The callbacks are run without the lock, so they may register other callbacks.
+        let callbacks = CACHE_READY_CALLBACKS.lock().clone();
+        drop(lock);
+        for callback in callbacks {
+            callback(env);
+        }
+    }
+    Ok(())
+}
+
+/// Registers the callback initializing a subsystem that depends on the cache, e.g.,
+/// the logging bridge. The callback is run with the environment initializing the cache
+/// right after it is initialized, or immediately (in the current thread, attached
+/// if needed) if the cache is already initialized.
+///
+/// The callbacks are run in the order of registration, and run again each time
+/// the cache is initialized after `reset_cache`.
+pub fn on_cache_ready(callback: fn(&JNIEnv)) {
+    {
+        let _lock = INIT_LOCK.lock();
+        CACHE_READY_CALLBACKS.lock().push(callback);
+        if !INITIALIZED.load(Ordering::Acquire) {
+            return;
+        }
+    }
+    with_attached_thread(callback);
+}
+
+thread_local! {
+    /// Whether the current thread is caching the methods, see `try_init_cache`.
+    static CACHING_ON_THIS_THREAD: Cell<bool> = Cell::new(false);
+}
+
+/// Marks the current thread as caching the methods until dropped (including unwinding).
+struct CachingGuard;
+
+impl CachingGuard {
+    fn new() -> Self {
+        CACHING_ON_THIS_THREAD.with(|caching| caching.set(true));
+        CachingGuard
+    }
+}
+
+impl Drop for CachingGuard {
+    fn drop(&mut self) {
+        CACHING_ON_THIS_THREAD.with(|caching| caching.set(false));
+    }
+}
+
+/// The `ServiceRuntimeAdapter` and `BlockchainData` methods that must be present for
+/// the cache to be initialized.
+///
+/// By default all methods are required. If `EJB_REQUIRED_METHODS` environment variable
+/// is set, only the listed methods are (e.g., `initialize,executeTransaction`), and
+/// the missing methods not listed are left uncached: their `try_` accessors return
+/// an error. Methods of the other classes are always required.
+struct RequiredMethods(Option<Vec<String>>);
+
+impl RequiredMethods {
+    /// Reads the required methods from `EJB_REQUIRED_METHODS` environment variable.
+    fn from_env() -> Self {
+        match env::var(REQUIRED_METHODS_ENV) {
+            Ok(methods) => {
+                debug!("Required methods: {}", methods);
+                Self::parse(&methods)
+            }
+            Err(_) => RequiredMethods(None),
+        }
+    }
+
+    /// Parses the comma-separated list of method names.
+    fn parse(methods: &str) -> Self {
+        let methods = methods
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_owned)
+            .collect();
+        RequiredMethods(Some(methods))
+    }
+
+    /// Returns whether the method with the given name is required.
+    fn is_required(&self, name: &str) -> bool {
+        match &self.0 {
+            Some(methods) => methods.iter().any(|method| method == name),
+            None => true,
+        }
+    }
+}
+
+/// Stores the `JavaVM` of the given environment, see `java_vm`.
+fn cache_java_vm(env: &JNIEnv) {
+    let vm = env
+        .get_java_vm()
+        .expect("Cannot get reference to the JavaVM");
+    JAVA_VM.store(Box::into_raw(Box::new(vm)), Ordering::Release);
+}
+
+/// Returns the `JavaVM` the cache is initialized against, allowing native threads
+/// to attach to it.
+///
+/// Panics if the cache is not initialized.
+pub fn java_vm() -> &'static JavaVM {
+    try_java_vm().expect("JNI cache is not initialized")
+}
+
+/// Returns the cached `JavaVM`, or `None` if it is not stored (e.g., the cache is mocked).
+fn try_java_vm() -> Option<&'static JavaVM> {
+    let vm = JAVA_VM.load(Ordering::Acquire);
+    // Safe as the stored `JavaVM` is never deallocated.
+    unsafe { vm.as_ref() }
+}
+
+/// Runs the closure with the `JNIEnv` of the current thread, attaching it to the cached
+/// `JavaVM` as a daemon thread if it is detached (so that it does not prevent
+/// the JVM from exiting). The thread is detached after the closure returns or panics,
+/// unless it was attached before the call.
+///
+/// Panics if the cache is not initialized or the thread cannot be attached.
+pub fn with_attached_thread<F, R>(f: F) -> R
+where
+    F: FnOnce(&JNIEnv) -> R,
+{
+    with_attached_vm(java_vm(), f)
+}
+
+/// Runs the closure with the `JNIEnv` of the current thread attached to the given `JavaVM`,
+/// see `with_attached_thread`.
+fn with_attached_vm<F, R>(vm: &'static JavaVM, f: F) -> R
+where
+    F: FnOnce(&JNIEnv) -> R,
+{
+    match vm.get_env() {
+        Ok(env) => f(&env),
+        Err(JniError(JniErrorKind::ThreadDetached, _)) => {
+            let env = vm
+                .attach_current_thread_as_daemon()
+                .expect("Cannot attach the current thread to the JavaVM");
+            let _detach = DetachGuard(vm);
+            f(&env)
+        }
+        Err(e) => panic!("Cannot get JNIEnv of the current thread: {}", e),
+    }
+}
+
+/// Detaches the current thread from the JVM when dropped (including unwinding).
+/// Must be created only by the code that attached the thread, see `with_attached_thread`.
+struct DetachGuard(&'static JavaVM);
+
+impl Drop for DetachGuard {
+    fn drop(&mut self) {
+        let vm = self.0.get_java_vm_pointer();
+        // Safe as the thread was attached by the owner of the guard, and `JNIEnv`
+        // of the thread is not used after the guard is dropped.
+        unsafe {
+            let detach = (**vm)
+                .DetachCurrentThread
+                .expect("DetachCurrentThread is not available");
+            if detach(vm) != JNI_OK {
+                error!("Failed to detach the current thread from the JavaVM");
+            }
+        }
+    }
+}
+
+/// Returns the duration of the last successful initialization of the cache
+/// (including loading of the cached classes), or `None` if it was never initialized.
+pub fn last_cache_duration() -> Option<Duration> {
+    *LAST_CACHE_DURATION.lock()
+}
+
+/// Returns the number of successful initializations of the cache, including the ones
+/// after `reset_cache`; failed attempts to cache the methods are not counted.
+/// The generation `1` is the cold load, involving the loading of the cached classes
+/// by the JVM; the subsequent ones are reloads.
+///
+/// Returns zero if the cache was never initialized.
+pub fn cache_generation() -> usize {
+    CACHE_GENERATION.load(Ordering::Acquire)
+}
+
+/// Returns `true` if the native library was initialized again from an environment
+/// in which `ServiceRuntimeAdapter` resolves to a class other than the cached one.
+///
+/// That happens when the library is loaded by several class loaders within one process:
+/// the cache is shared by the whole process and refers to the classes of the class loader
+/// that loaded the library first, hence the ids are invalid for the other loaders.
+pub fn loader_mismatch_detected() -> bool {
+    LOADER_MISMATCH.load(Ordering::Acquire)
+}
+
+/// Compares `ServiceRuntimeAdapter` resolved in the given environment with the cached
+/// class and records a mismatch. Must be called with `INIT_LOCK` held and the cache
+/// initialized.
+fn detect_loader_mismatch(env: &JNIEnv) {
+    let adapter_class = adapter_class();
+    let current_class = match env.find_class(&adapter_class) {
+        Ok(class) => class,
+        Err(_) => {
+            // The class is not visible in this environment, nothing to compare with.
+            let _ = env.exception_clear();
+            return;
+        }
+    };
+    let cached_class = unsafe { SERVICE_RUNTIME_ADAPTER.as_ref() }
+        .expect("ServiceRuntimeAdapter class must be cached");
+    let is_same = env
+        .is_same_object(current_class, cached_class.as_obj())
+        .unwrap_or(false);
+    let _ = env.delete_local_ref(current_class.into());
+
+    if !is_same {
+        LOADER_MISMATCH.store(true, Ordering::Release);
+        warn!(
+            "The native library is loaded by several class loaders: {} resolved in the \
+             current environment differs from the cached one. The JNI cache is shared by \
+             the process and refers to the classes of the first class loader, so calls from \
+             the other class loaders will use invalid ids. Load the native library \
+             from a single (e.g., the system) class loader.",
+            adapter_class
+        );
+    }
+}
+
+/// Drops the cached class references and resets the cached method ids, so that the next
+/// `JNI_OnLoad` (or `init_cache`) caches them again, e.g., against a restarted JVM.
+///
+/// Must be called only while no other thread uses the cached ids and references,
+/// and before the JVM they belong to is destroyed, as dropping the class references
+/// requires the JVM. `JNI_OnUnload` calls this function automatically.
+///
+/// Waits (at most `RESET_TIMEOUT`) for the adapter calls in progress (see `AdapterCallGuard`)
+/// to complete before releasing the cache. The adapter calls starting once the reset
+/// has begun are rejected.
+///
+/// It is safe to call this function even if the cache was never (or only partially)
+/// initialized.
+pub fn reset_cache() {
+    let _resetting = ResettingGuard::new();
+    wait_for_adapter_calls(RESET_TIMEOUT);
+    let _lock = INIT_LOCK.lock();
+    INITIALIZED.store(false, Ordering::Release);
+    LOADER_MISMATCH.store(false, Ordering::Release);
+    // The `JavaVM` is leaked intentionally, as references returned by `java_vm` may be alive.
+    JAVA_VM.store(ptr::null_mut(), Ordering::Release);
+    unsafe { clear_methods() };
+    debug!("Released references to Java classes and methods.");
+}
+
+/// Marks `reset_cache` in progress until dropped (including unwinding).
+struct ResettingGuard(());
+
+impl ResettingGuard {
+    fn new() -> Self {
+        // Sequentially consistent with the counter of the adapter calls, so that either
+        // `try_adapter_call_guard` sees the reset, or the reset waits for the call.
+        RESETS_IN_PROGRESS.fetch_add(1, Ordering::SeqCst);
+        ResettingGuard(())
+    }
+}
+
+impl Drop for ResettingGuard {
+    fn drop(&mut self) {
+        RESETS_IN_PROGRESS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Returns a guard marking an adapter call in progress until dropped. `reset_cache` waits
+/// for such calls to complete, so the cache is not released while it is used.
+///
+/// Must be held around each call of the cached `ServiceRuntimeAdapter` methods.
+///
+/// Panics if the cache is being reset, see `try_adapter_call_guard`.
+pub fn adapter_call_guard() -> AdapterCallGuard {
+    try_adapter_call_guard().unwrap_or_else(|_| panic!("JNI cache is being reset"))
+}
+
+/// Returns a guard marking an adapter call in progress until dropped,
+/// see `adapter_call_guard`.
+///
+/// Returns `NotInitialized` error if the cache is being reset, as the cached ids
+/// and references may be released at any moment.
+pub fn try_adapter_call_guard() -> Result<AdapterCallGuard, CacheError> {
+    IN_FLIGHT_CALLS.fetch_add(1, Ordering::SeqCst);
+    let guard = AdapterCallGuard(());
+    if RESETS_IN_PROGRESS.load(Ordering::SeqCst) > 0 {
+        // The guard backs out, so the reset does not wait for this call.
+        drop(guard);
+        return Err(CacheError::NotInitialized);
+    }
+    Ok(guard)
+}
+
+/// Marks an adapter call in progress, see `adapter_call_guard`.
+#[derive(Debug)]
+pub struct AdapterCallGuard(());
+
+impl Drop for AdapterCallGuard {
+    fn drop(&mut self) {
+        IN_FLIGHT_CALLS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Returns the number of adapter calls in progress.
+pub fn adapter_calls_in_progress() -> usize {
+    IN_FLIGHT_CALLS.load(Ordering::SeqCst)
+}
+
+/// Waits until there are no adapter calls in progress or the timeout elapses.
+fn wait_for_adapter_calls(timeout: Duration) {
+    let start = Instant::now();
+    while adapter_calls_in_progress() > 0 {
+        if start.elapsed() >= timeout {
+            warn!(
+                "Releasing JNI cache while {} adapter calls are still in progress",
+                adapter_calls_in_progress()
+            );
+            return;
+        }
+        std::thread::sleep(RESET_POLL_INTERVAL);
+    }
+}
+
+/// Caches all required classes and methods ids.
+///
+/// All the ids and references are resolved into locals first and assigned to the statics
+/// in a single block once every lookup succeeded, so a failure (or a panic) partway
+/// through leaves the cache empty rather than half-initialized.
+unsafe fn cache_methods(env: &JNIEnv) -> Result<(), CacheError> {
+    let object_to_string =
+        get_method_id(&env, "java/lang/Object", "toString", "()Ljava/lang/String;")?;
+    let thread_current_thread = get_static_method_id(
+        &env,
+        JAVA_LANG_THREAD_CLASS,
+        "currentThread",
+        "()Ljava/lang/Thread;",
+    )?;
+    let thread_get_context_class_loader = get_method_id(
+        &env,
+        JAVA_LANG_THREAD_CLASS,
+        "getContextClassLoader",
+        "()Ljava/lang/ClassLoader;",
+    )?;
+    let object_get_class =
+        get_method_id(&env, "java/lang/Object", "getClass", "()Ljava/lang/Class;")?;
+    let auto_closeable_close = get_method_id(&env, "java/lang/AutoCloseable", "close", "()V")?;
+    let class_get_name = get_method_id(&env, "java/lang/Class", "getName", "()Ljava/lang/String;")?;
+    let class_get_simple_name = get_method_id(
+        &env,
+        JAVA_LANG_CLASS_NAME,
+        "getSimpleName",
+        "()Ljava/lang/String;",
+    )?;
+    let class_is_assignable_from = get_method_id(
+        &env,
+        JAVA_LANG_CLASS_NAME,
+        "isAssignableFrom",
+        "(Ljava/lang/Class;)Z",
+    )?;
+    let throwable_get_message = get_method_id(
+        &env,
+        "java/lang/Throwable",
+        "getMessage",
+        "()Ljava/lang/String;",
+    )?;
+    let throwable_get_localized_message = get_method_id(
+        &env,
+        "java/lang/Throwable",
+        "getLocalizedMessage",
+        "()Ljava/lang/String;",
+    )?;
+    let throwable_get_cause = get_method_id(
+        &env,
+        "java/lang/Throwable",
+        "getCause",
+        "()Ljava/lang/Throwable;",
+    )?;
+    let throwable_get_stack_trace = get_method_id(
+        &env,
+        "java/lang/Throwable",
+        "getStackTrace",
+        "()[Ljava/lang/StackTraceElement;",
+    )?;
+    let stack_trace_element_to_string = get_method_id(
+        &env,
+        "java/lang/StackTraceElement",
+        "toString",
+        "()Ljava/lang/String;",
+    )?;
+    let throwable_add_suppressed = get_method_id(
+        &env,
+        "java/lang/Throwable",
+        "addSuppressed",
+        "(Ljava/lang/Throwable;)V",
+    )?;
+    let execution_exception_get_error_code =
+        get_method_id(&env, EXECUTION_EXCEPTION_CLASS, "getErrorCode", "()B")?;
+    let execution_exception_constructor = get_method_id(
+        &env,
+        EXECUTION_EXCEPTION_CLASS,
+        "<init>",
+        "(BLjava/lang/String;)V",
+    )?;
+    let unexpected_execution_exception_constructor = get_method_id(
+        &env,
+        UNEXPECTED_EXECUTION_EXCEPTION_CLASS,
+        "<init>",
+        "(Ljava/lang/Throwable;)V",
+    )?;
+
+    // The adapter class may be overridden, hence it is checked to exist before
+    // resolving its methods. Its name is needed to resolve them, so it is the only
+    // state set before the commit; `try_init_cache` clears it on failure.
+    let adapter_class = requested_adapter_class();
+    let service_runtime_adapter = get_class(env, &adapter_class)?;
+    *ADAPTER_CLASS.lock() = adapter_class;
+    let required = RequiredMethods::from_env();
+    let runtime_adapter_ids = runtime_adapter::lookup(env, &required)?;
+    let blockchain_data_ids = blockchain_data::lookup(env, &required)?;
+
+    let class_for_name = get_static_method_id(
+        &env,
+        JAVA_LANG_CLASS_NAME,
+        "forName",
+        "(Ljava/lang/String;ZLjava/lang/ClassLoader;)Ljava/lang/Class;",
+    )?;
+    let key_pair_new_instance = get_static_method_id(
+        &env,
+        KEY_PAIR_CLASS,
+        "newInstance",
+        "([B[B)Lcom/exonum/binding/common/crypto/KeyPair;",
+    )?;
+    let system_nano_time = get_static_method_id(&env, JAVA_LANG_SYSTEM_CLASS, "nanoTime", "()J")?;
+    let integer_value_of = get_static_method_id(
+        &env,
+        JAVA_LANG_INTEGER_CLASS,
+        "valueOf",
+        "(I)Ljava/lang/Integer;",
+    )?;
+    let long_value_of =
+        get_static_method_id(&env, JAVA_LANG_LONG_CLASS, "valueOf", "(J)Ljava/lang/Long;")?;
+    let integer_int_value = get_method_id(&env, JAVA_LANG_INTEGER_CLASS, "intValue", "()I")?;
+    let long_long_value = get_method_id(&env, JAVA_LANG_LONG_CLASS, "longValue", "()J")?;
+    let message_lite_to_byte_array = get_method_id(
+        &env,
+        "com/google/protobuf/MessageLite",
+        "toByteArray",
+        "()[B",
+    )?;
+    let array_list_constructor = get_method_id(&env, JAVA_UTIL_ARRAY_LIST_CLASS, "<init>", "(I)V")?;
+    let list_add = get_method_id(&env, JAVA_UTIL_LIST_CLASS, "add", "(Ljava/lang/Object;)Z")?;
+    let list_size = get_method_id(&env, JAVA_UTIL_LIST_CLASS, "size", "()I")?;
+    let map_entry_get_key = get_method_id(
+        &env,
+        JAVA_UTIL_MAP_ENTRY_CLASS,
+        "getKey",
+        "()Ljava/lang/Object;",
+    )?;
+    let map_entry_get_value = get_method_id(
+        &env,
+        JAVA_UTIL_MAP_ENTRY_CLASS,
+        "getValue",
+        "()Ljava/lang/Object;",
+    )?;
+    let char_sequence_length = get_method_id(&env, JAVA_LANG_CHAR_SEQUENCE_CLASS, "length", "()I")?;
+    let char_sequence_to_string = get_method_id(
+        &env,
+        JAVA_LANG_CHAR_SEQUENCE_CLASS,
+        "toString",
+        "()Ljava/lang/String;",
+    )?;
+    let method_get_name = get_method_id(
+        &env,
+        JAVA_LANG_REFLECT_METHOD_CLASS,
+        "getName",
+        "()Ljava/lang/String;",
+    )?;
+    let method_get_parameter_types = get_method_id(
+        &env,
+        JAVA_LANG_REFLECT_METHOD_CLASS,
+        "getParameterTypes",
+        "()[Ljava/lang/Class;",
+    )?;
+
+    let execution_exception_error_code =
+        get_field_id(&env, EXECUTION_EXCEPTION_CLASS, "errorCode", "B")?;
+
+    let java_lang_class = get_class(env, JAVA_LANG_CLASS_NAME)?;
+    let java_lang_error = get_class(env, JAVA_LANG_ERROR_CLASS)?;
+    let java_lang_system = get_class(env, JAVA_LANG_SYSTEM_CLASS)?;
+    let java_lang_integer = get_class(env, JAVA_LANG_INTEGER_CLASS)?;
+    let java_lang_long = get_class(env, JAVA_LANG_LONG_CLASS)?;
+    let java_util_array_list = get_class(env, JAVA_UTIL_ARRAY_LIST_CLASS)?;
+    let java_lang_runtime_exception = get_class(env, JAVA_LANG_RUNTIME_EXCEPTION_CLASS)?;
+    let java_lang_illegal_argument_exception =
+        get_class(env, JAVA_LANG_ILLEGAL_ARGUMENT_EXCEPTION_CLASS)?;
+    let java_lang_illegal_state_exception =
+        get_class(env, JAVA_LANG_ILLEGAL_STATE_EXCEPTION_CLASS)?;
+    let java_util_no_such_element_exception =
+        get_class(env, JAVA_UTIL_NO_SUCH_ELEMENT_EXCEPTION_CLASS)?;
+    let java_lang_index_out_of_bounds_exception =
+        get_class(env, JAVA_LANG_INDEX_OUT_OF_BOUNDS_EXCEPTION_CLASS)?;
+    let execution_exception = get_class(env, EXECUTION_EXCEPTION_CLASS)?;
+    let unexpected_execution_exception = get_class(env, UNEXPECTED_EXECUTION_EXCEPTION_CLASS)?;
+    let key_pair = get_class(env, KEY_PAIR_CLASS)?;
+    let boolean_true =
+        get_static_field_ref(env, JAVA_LANG_BOOLEAN_CLASS, "TRUE", "Ljava/lang/Boolean;")?;
+    let boolean_false =
+        get_static_field_ref(env, JAVA_LANG_BOOLEAN_CLASS, "FALSE", "Ljava/lang/Boolean;")?;
+
+    #[cfg(feature = "java-logging")]
+    let log_bridge_ids = log_bridge::lookup(env)?;
+
+    // Every lookup succeeded: commit the cache.
+    OBJECT_TO_STRING = Some(object_to_string);
+    THREAD_CURRENT_THREAD = Some(thread_current_thread);
+    THREAD_GET_CONTEXT_CLASS_LOADER = Some(thread_get_context_class_loader);
+    OBJECT_GET_CLASS = Some(object_get_class);
+    AUTO_CLOSEABLE_CLOSE = Some(auto_closeable_close);
+    CLASS_GET_NAME = Some(class_get_name);
+    CLASS_GET_SIMPLE_NAME = Some(class_get_simple_name);
+    CLASS_IS_ASSIGNABLE_FROM = Some(class_is_assignable_from);
+    THROWABLE_GET_MESSAGE = Some(throwable_get_message);
+    THROWABLE_GET_LOCALIZED_MESSAGE = Some(throwable_get_localized_message);
+    THROWABLE_GET_CAUSE = Some(throwable_get_cause);
+    THROWABLE_GET_STACK_TRACE = Some(throwable_get_stack_trace);
+    STACK_TRACE_ELEMENT_TO_STRING = Some(stack_trace_element_to_string);
+    THROWABLE_ADD_SUPPRESSED = Some(throwable_add_suppressed);
+    EXECUTION_EXCEPTION_GET_ERROR_CODE = Some(execution_exception_get_error_code);
+    EXECUTION_EXCEPTION_CONSTRUCTOR = Some(execution_exception_constructor);
+    UNEXPECTED_EXECUTION_EXCEPTION_CONSTRUCTOR = Some(unexpected_execution_exception_constructor);
+    SERVICE_RUNTIME_ADAPTER = Some(service_runtime_adapter);
+    runtime_adapter::commit(runtime_adapter_ids);
+    blockchain_data::commit(blockchain_data_ids);
+    CLASS_FOR_NAME = Some(class_for_name);
+    KEY_PAIR_NEW_INSTANCE = Some(key_pair_new_instance);
+    SYSTEM_NANO_TIME = Some(system_nano_time);
+    INTEGER_VALUE_OF = Some(integer_value_of);
+    LONG_VALUE_OF = Some(long_value_of);
+    INTEGER_INT_VALUE = Some(integer_int_value);
+    LONG_LONG_VALUE = Some(long_long_value);
+    MESSAGE_LITE_TO_BYTE_ARRAY = Some(message_lite_to_byte_array);
+    ARRAY_LIST_CONSTRUCTOR = Some(array_list_constructor);
+    LIST_ADD = Some(list_add);
+    LIST_SIZE = Some(list_size);
+    MAP_ENTRY_GET_KEY = Some(map_entry_get_key);
+    MAP_ENTRY_GET_VALUE = Some(map_entry_get_value);
+    CHAR_SEQUENCE_LENGTH = Some(char_sequence_length);
+    CHAR_SEQUENCE_TO_STRING = Some(char_sequence_to_string);
+    METHOD_GET_NAME = Some(method_get_name);
+    METHOD_GET_PARAMETER_TYPES = Some(method_get_parameter_types);
+    EXECUTION_EXCEPTION_ERROR_CODE = Some(execution_exception_error_code);
+    JAVA_LANG_CLASS = Some(java_lang_class);
+    JAVA_LANG_ERROR = Some(java_lang_error);
+    JAVA_LANG_SYSTEM = Some(java_lang_system);
+    JAVA_LANG_INTEGER = Some(java_lang_integer);
+    JAVA_LANG_LONG = Some(java_lang_long);
+    JAVA_UTIL_ARRAY_LIST = Some(java_util_array_list);
+    JAVA_LANG_RUNTIME_EXCEPTION = Some(java_lang_runtime_exception);
+    JAVA_LANG_ILLEGAL_ARGUMENT_EXCEPTION = Some(java_lang_illegal_argument_exception);
+    JAVA_LANG_ILLEGAL_STATE_EXCEPTION = Some(java_lang_illegal_state_exception);
+    JAVA_UTIL_NO_SUCH_ELEMENT_EXCEPTION = Some(java_util_no_such_element_exception);
+    JAVA_LANG_INDEX_OUT_OF_BOUNDS_EXCEPTION = Some(java_lang_index_out_of_bounds_exception);
+    EXECUTION_EXCEPTION = Some(execution_exception);
+    UNEXPECTED_EXECUTION_EXCEPTION = Some(unexpected_execution_exception);
+    KEY_PAIR = Some(key_pair);
+    BOOLEAN_TRUE = Some(boolean_true);
+    BOOLEAN_FALSE = Some(boolean_false);
+    EXCEPTION_CLASSES = vec![
+        (EXECUTION_EXCEPTION_CLASS, cached_ref(&EXECUTION_EXCEPTION)),
+        (
+            UNEXPECTED_EXECUTION_EXCEPTION_CLASS,
+            cached_ref(&UNEXPECTED_EXECUTION_EXCEPTION),
+        ),
+        (
+            JAVA_LANG_ILLEGAL_ARGUMENT_EXCEPTION_CLASS,
+            cached_ref(&JAVA_LANG_ILLEGAL_ARGUMENT_EXCEPTION),
+        ),
+        (
+            JAVA_LANG_ILLEGAL_STATE_EXCEPTION_CLASS,
+            cached_ref(&JAVA_LANG_ILLEGAL_STATE_EXCEPTION),
+        ),
+        (
+            JAVA_UTIL_NO_SUCH_ELEMENT_EXCEPTION_CLASS,
+            cached_ref(&JAVA_UTIL_NO_SUCH_ELEMENT_EXCEPTION),
+        ),
+        (
+            JAVA_LANG_INDEX_OUT_OF_BOUNDS_EXCEPTION_CLASS,
+            cached_ref(&JAVA_LANG_INDEX_OUT_OF_BOUNDS_EXCEPTION),
+        ),
+        (
+            JAVA_LANG_RUNTIME_EXCEPTION_CLASS,
+            cached_ref(&JAVA_LANG_RUNTIME_EXCEPTION),
+        ),
+        (JAVA_LANG_ERROR_CLASS, cached_ref(&JAVA_LANG_ERROR)),
+    ];
+    #[cfg(feature = "java-logging")]
+    log_bridge::commit(log_bridge_ids);
+
+    debug!("Done caching references to Java classes and methods.");
+    Ok(())
+}
+
+/// Resets all cached method ids and drops the cached class references.
+unsafe fn clear_methods() {
+    CACHED_ENTRIES.lock().clear();
+    ADAPTER_CLASS.lock().clear();
+
+    OBJECT_GET_CLASS = None;
+    OBJECT_TO_STRING = None;
+    THREAD_GET_CONTEXT_CLASS_LOADER = None;
+    THREAD_CURRENT_THREAD = None;
+    AUTO_CLOSEABLE_CLOSE = None;
+    CLASS_GET_NAME = None;
+    CLASS_GET_SIMPLE_NAME = None;
+    CLASS_IS_ASSIGNABLE_FROM = None;
+    THROWABLE_GET_MESSAGE = None;
+    THROWABLE_GET_LOCALIZED_MESSAGE = None;
+    THROWABLE_GET_CAUSE = None;
+    THROWABLE_GET_STACK_TRACE = None;
+    STACK_TRACE_ELEMENT_TO_STRING = None;
+    THROWABLE_ADD_SUPPRESSED = None;
+    EXECUTION_EXCEPTION_GET_ERROR_CODE = None;
+    EXECUTION_EXCEPTION_CONSTRUCTOR = None;
+    UNEXPECTED_EXECUTION_EXCEPTION_CONSTRUCTOR = None;
+    runtime_adapter::clear();
+    runtime_adapter::clear_lazy();
+    blockchain_data::clear();
+
+    CLASS_FOR_NAME = None;
+    KEY_PAIR_NEW_INSTANCE = None;
+    SYSTEM_NANO_TIME = None;
+    INTEGER_VALUE_OF = None;
+    LONG_VALUE_OF = None;
+    INTEGER_INT_VALUE = None;
+    LONG_LONG_VALUE = None;
+    MESSAGE_LITE_TO_BYTE_ARRAY = None;
+    ARRAY_LIST_CONSTRUCTOR = None;
+    LIST_ADD = None;
+    LIST_SIZE = None;
+    MAP_ENTRY_GET_KEY = None;
+    MAP_ENTRY_GET_VALUE = None;
+    CHAR_SEQUENCE_LENGTH = None;
+    CHAR_SEQUENCE_TO_STRING = None;
+    METHOD_GET_NAME = None;
+    METHOD_GET_PARAMETER_TYPES = None;
+
+    EXECUTION_EXCEPTION_ERROR_CODE = None;
+
+    strings::clear_interned();
+    adapters::clear();
+    service_classes::clear();
+    #[cfg(feature = "java-logging")]
+    log_bridge::clear();
+
+    EXCEPTION_CLASSES = Vec::new();
+    JAVA_LANG_CLASS = None;
+    JAVA_LANG_ERROR = None;
+    JAVA_LANG_SYSTEM = None;
+    JAVA_LANG_INTEGER = None;
+    JAVA_LANG_LONG = None;
+    JAVA_UTIL_ARRAY_LIST = None;
+    JAVA_LANG_RUNTIME_EXCEPTION = None;
+    JAVA_LANG_ILLEGAL_ARGUMENT_EXCEPTION = None;
+    JAVA_LANG_ILLEGAL_STATE_EXCEPTION = None;
+    JAVA_UTIL_NO_SUCH_ELEMENT_EXCEPTION = None;
+    JAVA_LANG_INDEX_OUT_OF_BOUNDS_EXCEPTION = None;
+    EXECUTION_EXCEPTION = None;
+    UNEXPECTED_EXECUTION_EXCEPTION = None;
+    KEY_PAIR = None;
+    BOOLEAN_TRUE = None;
+    BOOLEAN_FALSE = None;
+    SERVICE_RUNTIME_ADAPTER = None;
+}
+
+/// Returns `true` if none of the eagerly cached classes, methods and fields is set,
+/// i.e., the cache was never initialized, was reset, or failed to initialize.
+///
+/// Unlike `is_cache_initialized`, inspects the cached ids themselves; intended for
+/// the tests of the cache initialization.
+#[doc(hidden)]
+pub fn is_cache_empty() -> bool {
+    unsafe {
+        let empty = OBJECT_GET_CLASS.is_none()
+            && OBJECT_TO_STRING.is_none()
+            && THREAD_GET_CONTEXT_CLASS_LOADER.is_none()
+            && THREAD_CURRENT_THREAD.is_none()
+            && AUTO_CLOSEABLE_CLOSE.is_none()
+            && CLASS_GET_NAME.is_none()
+            && CLASS_GET_SIMPLE_NAME.is_none()
+            && CLASS_IS_ASSIGNABLE_FROM.is_none()
+            && THROWABLE_GET_MESSAGE.is_none()
+            && THROWABLE_GET_LOCALIZED_MESSAGE.is_none()
+            && THROWABLE_GET_CAUSE.is_none()
+            && THROWABLE_GET_STACK_TRACE.is_none()
+            && STACK_TRACE_ELEMENT_TO_STRING.is_none()
+            && THROWABLE_ADD_SUPPRESSED.is_none()
+            && EXECUTION_EXCEPTION_GET_ERROR_CODE.is_none()
+            && EXECUTION_EXCEPTION_CONSTRUCTOR.is_none()
+            && UNEXPECTED_EXECUTION_EXCEPTION_CONSTRUCTOR.is_none()
+            && runtime_adapter::is_clear()
+            && blockchain_data::is_clear()
+            && CLASS_FOR_NAME.is_none()
+            && KEY_PAIR_NEW_INSTANCE.is_none()
+            && SYSTEM_NANO_TIME.is_none()
+            && INTEGER_VALUE_OF.is_none()
+            && LONG_VALUE_OF.is_none()
+            && INTEGER_INT_VALUE.is_none()
+            && LONG_LONG_VALUE.is_none()
+            && MESSAGE_LITE_TO_BYTE_ARRAY.is_none()
+            && ARRAY_LIST_CONSTRUCTOR.is_none()
+            && LIST_ADD.is_none()
+            && LIST_SIZE.is_none()
+            && MAP_ENTRY_GET_KEY.is_none()
+            && MAP_ENTRY_GET_VALUE.is_none()
+            && CHAR_SEQUENCE_LENGTH.is_none()
+            && CHAR_SEQUENCE_TO_STRING.is_none()
+            && METHOD_GET_NAME.is_none()
+            && METHOD_GET_PARAMETER_TYPES.is_none()
+            && EXECUTION_EXCEPTION_ERROR_CODE.is_none()
+            && JAVA_LANG_CLASS.is_none()
+            && JAVA_LANG_ERROR.is_none()
+            && JAVA_LANG_SYSTEM.is_none()
+            && JAVA_LANG_INTEGER.is_none()
+            && JAVA_LANG_LONG.is_none()
+            && JAVA_UTIL_ARRAY_LIST.is_none()
+            && JAVA_LANG_RUNTIME_EXCEPTION.is_none()
+            && JAVA_LANG_ILLEGAL_ARGUMENT_EXCEPTION.is_none()
+            && JAVA_LANG_ILLEGAL_STATE_EXCEPTION.is_none()
+            && JAVA_UTIL_NO_SUCH_ELEMENT_EXCEPTION.is_none()
+            && JAVA_LANG_INDEX_OUT_OF_BOUNDS_EXCEPTION.is_none()
+            && EXECUTION_EXCEPTION.is_none()
+            && UNEXPECTED_EXECUTION_EXCEPTION.is_none()
+            && KEY_PAIR.is_none()
+            && BOOLEAN_TRUE.is_none()
+            && BOOLEAN_FALSE.is_none()
+            && SERVICE_RUNTIME_ADAPTER.is_none()
+            && EXCEPTION_CLASSES.is_empty();
+        #[cfg(feature = "java-logging")]
+        let empty = empty && log_bridge::is_clear();
+        empty
+    }
+}
+
+/// Returns the cached class (or static field value) reference.
+///
+/// Panics if the reference is not cached.
+fn cached_ref(class_ref: &Option<CountedRef>) -> GlobalRef {
+    GlobalRef::clone(class_ref.as_ref().expect("Reference is not cached"))
+}
+
+/// Produces `JMethodID` for a particular method dealing with its lifetime.
+///
+/// Returns an error if method not found, clearing the pending `NoSuchMethodError`.
+fn get_method_id(
+    env: &JNIEnv,
+    class: &str,
+    name: &str,
+    sig: &str,
+) -> Result<JMethodID<'static>, CacheError> {
+    let method_id = env
+        .get_method_id(class, name, sig)
+        // we need this line to erase lifetime in order to save underlying raw pointer in static
+        .map(|mid| mid.into_inner().into())
+        .map_err(|_| {
+            let _ = env.exception_clear();
+            CacheError::MethodNotFound {
+                class: class.to_owned(),
+                name: name.to_owned(),
+                sig: sig.to_owned(),
+            }
+        })?;
+    record_entry(
+        CachedEntryKind::Method,
+        class,
+        name,
+        sig,
+        method_id.into_inner() as _,
+    );
+    Ok(method_id)
+}
+
+/// Produces `JMethodID` for a method of the cached class on the first access to it.
+///
+/// Returns an error if method not found, clearing the pending `NoSuchMethodError`.
+fn get_method_id_lazily(
+    env: &JNIEnv,
+    class_ref: &GlobalRef,
+    class: &str,
+    name: &str,
+    sig: &str,
+) -> Result<JMethodID<'static>, CacheError> {
+    let method_id: JMethodID<'static> = env
+        .get_method_id(class_ref, name, sig)
+        // we need this line to erase lifetime in order to save underlying raw pointer in static
+        .map(|mid| mid.into_inner().into())
+        .map_err(|_| {
+            let _ = env.exception_clear();
+            CacheError::MethodNotFound {
+                class: class.to_owned(),
+                name: name.to_owned(),
+                sig: sig.to_owned(),
+            }
+        })?;
+    record_entry(
+        CachedEntryKind::Method,
+        class,
+        name,
+        sig,
+        method_id.into_inner() as _,
+    );
+    Ok(method_id)
+}
+
+/// Produces `JStaticMethodID` for a particular static method dealing with its lifetime.
+///
+/// Returns an error if method not found, clearing the pending `NoSuchMethodError`.
+fn get_static_method_id(
+    env: &JNIEnv,
+    class: &str,
+    name: &str,
+    sig: &str,
+) -> Result<JStaticMethodID<'static>, CacheError> {
+    let method_id = env
+        .get_static_method_id(class, name, sig)
+        // we need this line to erase lifetime in order to save underlying raw pointer in static
+        .map(|mid| mid.into_inner().into())
+        .map_err(|_| {
+            let _ = env.exception_clear();
+            CacheError::MethodNotFound {
+                class: class.to_owned(),
+                name: name.to_owned(),
+                sig: sig.to_owned(),
+            }
+        })?;
+    record_entry(
+        CachedEntryKind::StaticMethod,
+        class,
+        name,
+        sig,
+        method_id.into_inner() as _,
+    );
+    Ok(method_id)
+}
+
+/// Produces `JFieldID` for a particular field dealing with its lifetime.
+///
+/// Returns an error if field not found, clearing the pending `NoSuchFieldError`.
+fn get_field_id(
+    env: &JNIEnv,
+    class: &str,
+    name: &str,
+    sig: &str,
+) -> Result<JFieldID<'static>, CacheError> {
+    let field_id = env
+        .get_field_id(class, name, sig)
+        // we need this line to erase lifetime in order to save underlying raw pointer in static
+        .map(|fid| fid.into_inner().into())
+        .map_err(|_| {
+            let _ = env.exception_clear();
+            CacheError::FieldNotFound {
+                class: class.to_owned(),
+                name: name.to_owned(),
+                sig: sig.to_owned(),
+            }
+        })?;
+    record_entry(
+        CachedEntryKind::Field,
+        class,
+        name,
+        sig,
+        field_id.into_inner() as _,
+    );
+    Ok(field_id)
+}
+
+/// Returns cached class reference.
+///
+/// The class is resolved with the class loader of the caller, so this function shall be used
+/// only for the classes available in `JNI_OnLoad`. See `get_class_with_loader` for other classes.
+///
+/// Returns an error if class not found, clearing the pending `NoClassDefFoundError`.
+fn get_class(env: &JNIEnv, class: &str) -> Result<CountedRef, CacheError> {
+    let class_object = env.find_class(class).map_err(|_| {
+        let _ = env.exception_clear();
+        CacheError::ClassNotFound {
+            class: class.to_owned(),
+            loader: describe_context_class_loader(env),
+        }
+    })?;
+    let class_ref = ref_metrics::new_global_ref(env, class_object)?;
+    record_entry(
+        CachedEntryKind::Class,
+        class,
+        "",
+        "",
+        class_ref.as_obj().into_inner() as _,
+    );
+    Ok(class_ref)
+}
+
+/// Returns a global reference to the value of a static object field, e.g.,
+/// of a singleton constant.
+///
+/// Returns an error if the class or field not found, clearing the pending error.
+fn get_static_field_ref(
+    env: &JNIEnv,
+    class: &str,
+    name: &str,
+    sig: &str,
+) -> Result<CountedRef, CacheError> {
+    let value = env
+        .get_static_field(class, name, sig)
+        .and_then(|value| value.l())
+        .map_err(|_| {
+            let _ = env.exception_clear();
+            CacheError::FieldNotFound {
+                class: class.to_owned(),
+                name: name.to_owned(),
+                sig: sig.to_owned(),
+            }
+        })?;
+    let value_ref = ref_metrics::new_global_ref(env, value)?;
+    record_entry(
+        CachedEntryKind::StaticField,
+        class,
+        name,
+        sig,
+        value_ref.as_obj().into_inner() as _,
+    );
+    Ok(value_ref)
+}
+
+/// Records the description of the cached entry for `cached_entries` and logs it
+/// along with the raw id (or reference) at trace level.
+fn record_entry(kind: CachedEntryKind, class: &str, name: &str, sig: &str, raw: *const c_void) {
+    trace!(
+        "Cached {:?} {}{}{}{} as {:p}",
+        kind,
+        class,
+        if name.is_empty() { "" } else { "." },
+        name,
+        sig,
+        raw
+    );
+    CACHED_ENTRIES.lock().push(CachedEntry {
+        class: class.to_owned(),
+        name: name.to_owned(),
+        sig: sig.to_owned(),
+        kind,
+    });
+}
+
+/// Returns the descriptions of all cached classes, methods and fields in the order
+/// of caching. Returns an empty vector if the cache is not initialized.
+///
+/// Intended for diagnostics of mismatches between the native library and the Java classes.
+pub fn cached_entries() -> Vec<CachedEntry> {
+    CACHED_ENTRIES.lock().clone()
+}
+
+/// Verifies that the cache is initialized and that each cached class, method and field
+/// can still be resolved, returning a human-readable report of the check.
+///
+/// The report starts with a summary line with the number of resolved entries, followed
+/// by a line per entry that failed to resolve. Intended for diagnostics from the Java
+/// side, see `nativeSelfTest`.
+pub fn self_test(env: &JNIEnv) -> String {
+    if !is_cache_initialized() {
+        return "JNI cache self-test: FAILED, the cache is not initialized".to_owned();
+    }
+    let entries = cached_entries();
+    let failures: Vec<String> = entries
+        .iter()
+        .filter_map(|entry| {
+            let resolved = match entry.kind {
+                CachedEntryKind::Method => env
+                    .get_method_id(entry.class.as_str(), &entry.name, &entry.sig)
+                    .map(|_| ()),
+                CachedEntryKind::StaticMethod => env
+                    .get_static_method_id(entry.class.as_str(), &entry.name, &entry.sig)
+                    .map(|_| ()),
+                CachedEntryKind::Field => env
+                    .get_field_id(entry.class.as_str(), &entry.name, &entry.sig)
+                    .map(|_| ()),
+                CachedEntryKind::Class => env.find_class(entry.class.as_str()).map(|_| ()),
+                CachedEntryKind::StaticField => env
+                    .get_static_field_id(entry.class.as_str(), &entry.name, &entry.sig)
+                    .map(|_| ()),
+            };
+            resolved.err().map(|e| {
+                let _ = env.exception_clear();
+                format!(
+                    "FAILED {:?} {}{}{}{}: {}",
+                    entry.kind,
+                    entry.class,
+                    if entry.name.is_empty() { "" } else { "." },
+                    entry.name,
+                    entry.sig,
+                    e
+                )
+            })
+        })
+        .collect();
+    let mut report = format!(
+        "JNI cache self-test: {} of {} entries resolved (generation {})",
+        entries.len() - failures.len(),
+        entries.len(),
+        cache_generation()
+    );
+    for failure in failures {
+        report.push('\n');
+        report.push_str(&failure);
+    }
+    report
+}
+
+/// Resolves the methods given as `(class, name, signature)` tuples (e.g.,
+/// `("java/lang/String", "length", "()I")`) and returns their ids in the same order,
+/// so that native extensions may cache their own methods the same way as this cache.
+///
+/// The ids are valid as long as their classes are loaded, so the caller shall keep
+/// references to the classes (e.g., obtained with `get_class_with_loader`) while it uses
+/// the ids. The classes are resolved with `FindClass`, i.e., with the class loader
+/// of the calling code. The methods are included in `cached_entries`.
+///
+/// Returns an error if any of the methods is not found, clearing the pending error.
+pub fn cache_custom_methods(
+    env: &JNIEnv,
+    entries: &[(&str, &str, &str)],
+) -> Result<Vec<JMethodID<'static>>, CacheError> {
+    entries
+        .iter()
+        .map(|(class, name, sig)| get_method_id(env, class, name, sig))
+        .collect()
+}
+
+/// Returns a class reference, resolving the class with the given class loader
+/// using `Class.forName`. The class is initialized if it was not already.
+///
+/// Allows caching of classes that are not available to `JNI_OnLoad`, e.g., classes
+/// of service artifacts, which are loaded by their own class loaders. `class` is
+/// a JNI name of the class, e.g., `com/exonum/binding/Foo`.
+///
+/// Returns an error if class not found, clearing the pending `ClassNotFoundException`.
+/// The returned reference is counted as held by the native library while it is alive
+/// (see `ref_metrics`).
+pub fn get_class_with_loader(
+    env: &JNIEnv,
+    class: &str,
+    loader: JObject,
+) -> Result<CountedRef, CacheError> {
+    let not_found = || {
+        let _ = env.exception_clear();
+        CacheError::ClassNotFound {
+            class: class.to_owned(),
+            loader: describe_class_loader(env, loader),
+        }
+    };
+    let name = JObject::from(
+        env.new_string(class.replace('/', "."))
+            .map_err(|_| not_found())?,
+    );
+    let class_object = env
+        .call_static_method_unchecked(
+            &classes_refs::java_lang_class(),
+            static_methods::class_for_name_id(),
+            JavaType::Object(JAVA_LANG_CLASS_NAME.into()),
+            &[JValue::from(name), JValue::from(true), JValue::from(loader)],
+        )
+        .and_then(JValue::l);
+    // The local references are deleted, as the function may be called many times
+    // in a single native call (e.g., by `warm_service_classes`).
+    let _ = env.delete_local_ref(name);
+    let class_object = class_object.map_err(|_| not_found())?;
+    let class_ref = ref_metrics::new_global_ref(env, class_object);
+    let _ = env.delete_local_ref(class_object);
+    Ok(class_ref?)
+}
+
+/// Returns the description of the context class loader of the current thread,
+/// e.g., `jdk.internal.loader.ClassLoaders$AppClassLoader@1b6d3586`.
+fn describe_context_class_loader(env: &JNIEnv) -> String {
+    // The class loader is described while caching, before the ids are committed,
+    // so they are resolved on the spot if not cached.
+    let method_ids = match unsafe { (THREAD_CURRENT_THREAD, THREAD_GET_CONTEXT_CLASS_LOADER) } {
+        (Some(current_thread_id), Some(get_context_class_loader_id)) => {
+            Ok((current_thread_id, get_context_class_loader_id))
+        }
+        _ => env
+            .get_static_method_id(
+                JAVA_LANG_THREAD_CLASS,
+                "currentThread",
+                "()Ljava/lang/Thread;",
+            )
+            .and_then(|current_thread_id| {
+                let get_context_class_loader_id = env.get_method_id(
+                    JAVA_LANG_THREAD_CLASS,
+                    "getContextClassLoader",
+                    "()Ljava/lang/ClassLoader;",
+                )?;
+                Ok((current_thread_id, get_context_class_loader_id))
+            }),
+    };
+    let loader = method_ids.and_then(|(current_thread_id, get_context_class_loader_id)| {
+        context_class_loader(env, current_thread_id, get_context_class_loader_id)
+    });
+    match loader {
+        Ok(loader) => describe_class_loader(env, loader),
+        Err(_) => {
+            let _ = env.exception_clear();
+            UNKNOWN_CLASS_LOADER.to_owned()
+        }
+    }
+}
+
+/// Returns the context class loader of the current thread using the given method ids.
+fn context_class_loader<'a>(
+    env: &JNIEnv<'a>,
+    current_thread_id: JStaticMethodID,
+    get_context_class_loader_id: JMethodID,
+) -> JniResult<JObject<'a>> {
+    let thread = env
+        .call_static_method_unchecked(
+            JAVA_LANG_THREAD_CLASS,
+            current_thread_id,
+            JavaType::Object(JAVA_LANG_THREAD_CLASS.into()),
+            &[],
+        )?
+        .l()?;
+    let loader = env.call_method_unchecked(
+        thread,
+        get_context_class_loader_id,
+        JavaType::Object("java/lang/ClassLoader".into()),
+        &[],
+    );
+    env.delete_local_ref(thread)?;
+    loader?.l()
+}
+
+/// Returns the description of the class loader using its `toString` method.
+fn describe_class_loader(env: &JNIEnv, loader: JObject) -> String {
+    if loader.is_null() {
+        return "bootstrap".to_owned();
+    }
+    let to_string_id = match unsafe { OBJECT_TO_STRING } {
+        Some(to_string_id) => Ok(to_string_id),
+        None => env.get_method_id("java/lang/Object", "toString", "()Ljava/lang/String;"),
+    };
+    to_string_id
+        .and_then(|to_string_id| {
+            env.call_method_unchecked(
+                loader,
+                to_string_id,
+                JavaType::Object("java/lang/String".into()),
+                &[],
+            )
+        })
+        .and_then(JValue::l)
+        .and_then(|description| convert_to_string(env, description))
+        .unwrap_or_else(|_| {
+            let _ = env.exception_clear();
+            UNKNOWN_CLASS_LOADER.to_owned()
+        })
+}
+
+#[cfg(all(feature = "mock-cache", not(debug_assertions)))]
+compile_error!("\"mock-cache\" feature is intended for tests and cannot be used in release builds");
+
+/// Marks the cache initialized without a JVM and sets all the cached method and field ids
+/// to a non-null sentinel, so the code checking the cache initialization can be unit-tested
+/// in the crates depending on this one. The class references are not set.
+///
+/// The sentinel ids are invalid: any JNI call using them leads to undefined behaviour.
+/// Available with "mock-cache" feature in debug builds only.
+#[cfg(feature = "mock-cache")]
+pub fn force_initialized_for_tests() {
+    let _lock = INIT_LOCK.lock();
+    unsafe { mock_methods() };
+    INITIALIZED.store(true, Ordering::Release);
+}
+
+/// Sets all the cached method and field ids to a non-null sentinel.
+#[cfg(feature = "mock-cache")]
+unsafe fn mock_methods() {
+    // The sentinel is never dereferenced.
+    const MOCK_ID: usize = 1;
+    let method_id: JMethodID<'static> = (MOCK_ID as jni::sys::jmethodID).into();
+    let static_method_id = JStaticMethodID::from(MOCK_ID as jni::sys::jmethodID);
+    let field_id = JFieldID::from(MOCK_ID as jni::sys::jfieldID);
+
+    OBJECT_GET_CLASS = Some(method_id);
+    OBJECT_TO_STRING = Some(method_id);
+    THREAD_GET_CONTEXT_CLASS_LOADER = Some(method_id);
+    AUTO_CLOSEABLE_CLOSE = Some(method_id);
+    CLASS_GET_NAME = Some(method_id);
+    CLASS_GET_SIMPLE_NAME = Some(method_id);
+    CLASS_IS_ASSIGNABLE_FROM = Some(method_id);
+    THROWABLE_GET_MESSAGE = Some(method_id);
+    THROWABLE_GET_LOCALIZED_MESSAGE = Some(method_id);
+    THROWABLE_GET_CAUSE = Some(method_id);
+    THROWABLE_GET_STACK_TRACE = Some(method_id);
+    STACK_TRACE_ELEMENT_TO_STRING = Some(method_id);
+    THROWABLE_ADD_SUPPRESSED = Some(method_id);
+    EXECUTION_EXCEPTION_GET_ERROR_CODE = Some(method_id);
+    EXECUTION_EXCEPTION_CONSTRUCTOR = Some(method_id);
+    UNEXPECTED_EXECUTION_EXCEPTION_CONSTRUCTOR = Some(method_id);
+    INTEGER_INT_VALUE = Some(method_id);
+    LONG_LONG_VALUE = Some(method_id);
+    MESSAGE_LITE_TO_BYTE_ARRAY = Some(method_id);
+    ARRAY_LIST_CONSTRUCTOR = Some(method_id);
+    LIST_ADD = Some(method_id);
+    LIST_SIZE = Some(method_id);
+    MAP_ENTRY_GET_KEY = Some(method_id);
+    MAP_ENTRY_GET_VALUE = Some(method_id);
+    CHAR_SEQUENCE_LENGTH = Some(method_id);
+    CHAR_SEQUENCE_TO_STRING = Some(method_id);
+    METHOD_GET_NAME = Some(method_id);
+    METHOD_GET_PARAMETER_TYPES = Some(method_id);
+    CLASS_FOR_NAME = Some(static_method_id);
+    THREAD_CURRENT_THREAD = Some(static_method_id);
+    KEY_PAIR_NEW_INSTANCE = Some(static_method_id);
+    SYSTEM_NANO_TIME = Some(static_method_id);
+    INTEGER_VALUE_OF = Some(static_method_id);
+    LONG_VALUE_OF = Some(static_method_id);
+    EXECUTION_EXCEPTION_ERROR_CODE = Some(field_id);
+    runtime_adapter::mock(method_id);
+    runtime_adapter::mock_lazy(method_id);
+    blockchain_data::mock(method_id);
+    #[cfg(feature = "java-logging")]
+    log_bridge::mock(method_id, static_method_id);
+}
+
+/// Returns `true` if the cache is initialized and its accessors may be used.
+pub fn is_cache_initialized() -> bool {
+    INITIALIZED.load(Ordering::Acquire)
+}
+
+/// Checks that the cache is initialized at the entry of an exported native function,
+/// e.g., if the function is registered with `RegisterNatives` and called before
+/// `JNI_OnLoad` of the native library. Must be called inside `panic::catch_unwind`,
+/// so that the failure is thrown to Java as `RuntimeException` (see `unwrap_exc_or`).
+///
+/// Panics with an explicit message if the cache is not initialized. With "lazy-init"
+/// feature, initializes the cache from the given environment instead (panicking
+/// if that fails, see `init_cache`).
+pub fn ensure_loaded(env: &JNIEnv) {
+    if is_cache_initialized() {
+        return;
+    }
+    if cfg!(feature = "lazy-init") {
+        warn!("JNI cache is not initialized on a native call, initializing it");
+        init_cache(env);
+    } else {
+        panic!(
+            "JNI cache is not initialized: a native function is called before JNI_OnLoad \
+             of the native library"
+        );
+    }
+}
+
+fn check_cache_initialized() {
+    if !is_cache_initialized() {
+        panic!("JNI cache is not initialized")
+    }
+}
+
+/// Unwraps the cached method id, checking in debug builds that it is not null,
+/// so a corrupted cache is detected at the accessor rather than at the JNI call.
+fn cached_method_id(method_id: Option<JMethodID<'static>>) -> JMethodID<'static> {
+    let method_id = method_id.unwrap();
+    debug_assert!(
+        !method_id.into_inner().is_null(),
+        "Cached method id is null"
+    );
+    method_id
+}
+
+/// Refers to the cached methods of the `ServiceRuntimeAdapter` class.
+pub mod runtime_adapter {
+    use super::*;
+    use crate::Handle;
+    use jni::signature::Primitive;
+
+    cache_methods! {
+        /// Returns cached `JMethodID` for `ServiceRuntimeAdapter` constructor.
+        RUNTIME_ADAPTER_CONSTRUCTOR: (
+            &adapter_class(),
+            "<init>",
+            "(Lcom/exonum/binding/core/runtime/ServiceRuntime;\
+              Lcom/exonum/binding/core/runtime/AccessFactory;)V",
+        ) => constructor_id, try_constructor_id;
+        /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.initialize()`.
+        RUNTIME_ADAPTER_INITIALIZE: (
+            &adapter_class(),
+            "initialize",
+            "(J)V",
+        ) => initialize_id, try_initialize_id;
+        /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.deployArtifact()`.
+        RUNTIME_ADAPTER_DEPLOY_ARTIFACT: (
+            &adapter_class(),
+            "deployArtifact",
+            "([B[B)V",
+        ) => deploy_artifact_id, try_deploy_artifact_id;
+        /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.isArtifactDeployed()`.
+        RUNTIME_ADAPTER_IS_ARTIFACT_DEPLOYED: (
+            &adapter_class(),
+            "isArtifactDeployed",
+            "([B)Z",
+        ) => is_artifact_deployed_id, try_is_artifact_deployed_id;
+        /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.initiateAddingService()`.
+        RUNTIME_ADAPTER_INITIATE_ADDING_SERVICE: (
+            &adapter_class(),
+            "initiateAddingService",
+            "(J[B[B)V",
+        ) => initiate_adding_service_id, try_initiate_adding_service_id;
+        /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.updateServiceStatus()`.
+        RUNTIME_ADAPTER_UPDATE_SERVICE_STATUS: (
+            &adapter_class(),
+            "updateServiceStatus",
+            "([B[B)V",
+        ) => update_service_status_id, try_update_service_status_id;
+        /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.executeTransaction()`.
+        RUNTIME_ADAPTER_EXECUTE_TX: (
+            &adapter_class(),
+            "executeTransaction",
+            "(ILjava/lang/String;I[BJI[B[B)V",
+        ) => execute_tx_id, try_execute_tx_id;
+        /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.beforeTransactions()`.
+        RUNTIME_ADAPTER_BEFORE_TRANSACTIONS: (
+            &adapter_class(),
+            "beforeTransactions",
+            "(IJ)V",
+        ) => before_transactions_id, try_before_transactions_id;
+        /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.afterTransactions()`.
+        RUNTIME_ADAPTER_AFTER_TRANSACTIONS: (
+            &adapter_class(),
+            "afterTransactions",
+            "(IJ)V",
+        ) => after_transactions_id, try_after_transactions_id;
+        /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.afterCommit()`.
+        RUNTIME_ADAPTER_AFTER_COMMIT: (
+            &adapter_class(),
+            "afterCommit",
+            "(JIJ)V",
+        ) => after_commit_id, try_after_commit_id;
+        /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.shutdown()`.
+        RUNTIME_ADAPTER_SHUTDOWN: (
+            &adapter_class(),
+            "shutdown",
+            "()V",
+        ) => shutdown_id, try_shutdown_id;
+    }
+
+    lazy_cache_methods! {
+        /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.initiateResumingService()`.
+        RUNTIME_ADAPTER_INITIATE_RESUMING_SERVICE: (
+            classes_refs::service_runtime_adapter,
+            &adapter_class(),
+            "initiateResumingService",
+            "(J[B[B)V",
+        ) => initiate_resuming_service_id, try_initiate_resuming_service_id;
+    }
+
+    /// Returns `true` if the object is an instance of the cached `ServiceRuntimeAdapter`
+    /// class (or its subclass), so the cached adapter methods may be invoked on it.
+    /// Returns `false` for the null object.
+    ///
+    /// Panics if there is some JNI error.
+    pub fn is_adapter(env: &JNIEnv, obj: JObject) -> bool {
+        crate::utils::is_instance_of_cached(env, obj, &classes_refs::service_runtime_adapter())
+    }
+
+    /// Instantiates `ServiceRuntimeAdapter` from the native code, using the cached class
+    /// and constructor, and returns a global reference to the new adapter.
+    ///
+    /// The constructor expects:
+    /// - `service_runtime` — the `ServiceRuntime` the adapter delegates the calls to
+    /// - `access_factory` — the `AccessFactory` creating the database accesses (`Snapshot`,
+    ///   `BlockchainData`) from the native handles passed to the adapter.
+    ///
+    /// Normally the adapter is created by the Java code (via Guice) and passed to the native
+    /// library; this function is intended for the embedding modes constructing it natively.
+    /// The returned reference is counted as held by the native library while it is alive
+    /// (see `ref_metrics`).
+    pub fn new_adapter(
+        env: &JNIEnv,
+        service_runtime: JObject,
+        access_factory: JObject,
+    ) -> JniResult<CountedRef> {
+        let adapter = env.new_object_unchecked(
+            &classes_refs::service_runtime_adapter(),
+            try_constructor_id()?,
+            &[JValue::from(service_runtime), JValue::from(access_factory)],
+        )?;
+        let global_ref = ref_metrics::new_global_ref(env, adapter);
+        env.delete_local_ref(adapter)?;
+        global_ref
+    }
+
+    /// Arguments of `ServiceRuntimeAdapter.executeTransaction()`.
+    #[derive(Clone, Copy, Debug)]
+    pub struct ExecuteTxArgs<'a> {
+        /// Id of the service instance executing the transaction.
+        pub service_id: i32,
+        /// Name of the interface the transaction belongs to.
+        pub interface_name: &'a str,
+        /// Id of the transaction method.
+        pub tx_id: i32,
+        /// Serialized transaction arguments.
+        pub arguments: &'a [u8],
+        /// Handle to the `BlockchainData` of the execution context.
+        pub blockchain_data_handle: Handle,
+        /// Id of the calling service instance, `0` for transactions.
+        pub caller_id: i32,
+        /// Hash of the transaction message.
+        pub message_hash: &'a [u8],
+        /// Public key of the transaction author.
+        pub author_pk: &'a [u8],
+    }
+
+    /// Error of `execute_transaction`.
+    #[derive(Debug)]
+    pub enum ExecuteTxError {
+        /// The interface name contains a NUL character, so it cannot be a valid name
+        /// of a Java interface.
+        InvalidInterfaceName(String),
+        /// The adapter object is null. Reported only with "strict-null-checks" feature,
+        /// see `is_null_adapter`.
+        NullAdapter,
+        /// JNI error occurred during the call, e.g., the transaction threw an exception.
+        Jni(JniError),
+    }
+
+    impl fmt::Display for ExecuteTxError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                ExecuteTxError::InvalidInterfaceName(name) => {
+                    write!(f, "Invalid interface name (contains NUL): {:?}", name)
+                }
+                ExecuteTxError::NullAdapter => write!(f, "ServiceRuntimeAdapter is null"),
+                ExecuteTxError::Jni(e) => write!(f, "Failed to execute transaction: {}", e),
+            }
+        }
+    }
+
+    impl Error for ExecuteTxError {}
+
+    impl From<JniError> for ExecuteTxError {
+        fn from(e: JniError) -> Self {
+            ExecuteTxError::Jni(e)
+        }
+    }
+
+    impl From<ExecuteTxError> for JniError {
+        fn from(e: ExecuteTxError) -> Self {
+            match e {
+                ExecuteTxError::InvalidInterfaceName(_) => JniErrorKind::Msg(e.to_string()).into(),
+                ExecuteTxError::NullAdapter => JniErrorKind::NullPtr(ADAPTER_NAME).into(),
+                ExecuteTxError::Jni(e) => e,
+            }
+        }
+    }
+
+    /// Name of the adapter in the null adapter errors.
+    const ADAPTER_NAME: &str = "ServiceRuntimeAdapter";
+
+    /// Returns `true` if the adapter object is null, which would crash the JVM (instead of
+    /// throwing) on a call of the cached method. The adapter is checked only if
+    /// "strict-null-checks" feature is enabled, as the call is made on every invocation;
+    /// returns `false` otherwise.
+    fn is_null_adapter(env: &JNIEnv, adapter: JObject) -> JniResult<bool> {
+        if cfg!(feature = "strict-null-checks") {
+            env.is_same_object(adapter, JObject::null())
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Calls `ServiceRuntimeAdapter.executeTransaction()` on the given adapter
+    /// with the given arguments.
+    ///
+    /// Releases the local references to the converted arguments after the call.
+    /// Returns `InvalidInterfaceName` error before doing any JNI calls if the interface
+    /// name contains a NUL character, and `NullAdapter` error if the adapter is null
+    /// and "strict-null-checks" feature is enabled.
+    pub fn execute_transaction(
+        env: &JNIEnv,
+        adapter: JObject,
+        args: ExecuteTxArgs,
+    ) -> Result<(), ExecuteTxError> {
+        if args.interface_name.contains('\0') {
+            return Err(ExecuteTxError::InvalidInterfaceName(
+                args.interface_name.to_owned(),
+            ));
+        }
+        if is_null_adapter(env, adapter)? {
+            return Err(ExecuteTxError::NullAdapter);
+        }
+        let interned_name = match strings::known_string(args.interface_name) {
+            Some(name) => Some(strings::intern_string(env, name)?),
+            None => None,
+        };
+        #[cfg(feature = "validate-on-first-call")]
+        validate_execute_tx_id(env);
+
+        // The frame releases the local references on any error, e.g., if the transaction
+        // throws an exception.
+        with_local_frame(env, DEFAULT_LOCAL_FRAME_CAPACITY, || {
+            let interface_name = match &interned_name {
+                Some(name) => name.as_obj(),
+                None => JObject::from(env.new_string(args.interface_name)?),
+            };
+            let arguments = JObject::from(env.byte_array_from_slice(args.arguments)?);
+            let message_hash = JObject::from(env.byte_array_from_slice(args.message_hash)?);
+            let author_pk = JObject::from(env.byte_array_from_slice(args.author_pk)?);
+            env.call_method_unchecked(
+                adapter,
+                try_execute_tx_id()?,
+                JavaType::Primitive(Primitive::Void),
+                &[
+                    JValue::from(args.service_id),
+                    JValue::from(interface_name),
+                    JValue::from(args.tx_id),
+                    JValue::from(arguments),
+                    JValue::from(args.blockchain_data_handle),
+                    JValue::from(args.caller_id),
+                    JValue::from(message_hash),
+                    JValue::from(author_pk),
+                ],
+            )?
+            .v()
+        })?;
+        Ok(())
+    }
+
+    /// Whether the cached `executeTransaction` id was validated, see `validate_execute_tx_id`.
+    #[cfg(feature = "validate-on-first-call")]
+    static EXECUTE_TX_VALIDATED: AtomicBool = AtomicBool::new(false);
+
+    /// Resolves `executeTransaction` again on the first call and logs a warning if the id
+    /// differs from the cached one. The subsequent calls are not checked.
+    #[cfg(feature = "validate-on-first-call")]
+    fn validate_execute_tx_id(env: &JNIEnv) {
+        if EXECUTE_TX_VALIDATED
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            return;
+        }
+        let (name, sig) = declared_methods()
+            .into_iter()
+            .find(|(name, _)| *name == "executeTransaction")
+            .expect("executeTransaction is not declared");
+        let cached = match try_execute_tx_id() {
+            Ok(cached) => cached,
+            Err(_) => return,
+        };
+        match env.get_method_id(&classes_refs::service_runtime_adapter(), name, sig) {
+            Ok(resolved) if resolved.into_inner() == cached.into_inner() => {
+                debug!("Validated the cached id of {}{}", name, sig)
+            }
+            Ok(_) => warn!(
+                "The cached id of {}{} differs from the resolved one",
+                name, sig
+            ),
+            Err(e) => {
+                let _ = env.exception_clear();
+                warn!("Failed to validate the cached id of {}{}: {}", name, sig, e)
+            }
+        }
+    }
+
+    /// Calls `ServiceRuntimeAdapter.isArtifactDeployed()` on the given adapter with
+    /// the serialized artifact id.
+    ///
+    /// Returns `NullPtr` error if the adapter is null and "strict-null-checks" feature
+    /// is enabled.
+    pub fn is_artifact_deployed(
+        env: &JNIEnv,
+        adapter: JObject,
+        artifact_id: &[u8],
+    ) -> JniResult<bool> {
+        if is_null_adapter(env, adapter)? {
+            return Err(JniErrorKind::NullPtr(ADAPTER_NAME).into());
+        }
+        let artifact_id = JObject::from(env.byte_array_from_slice(artifact_id)?);
+        let result = env.call_method_unchecked(
+            adapter,
+            is_artifact_deployed_id(),
+            JavaType::Primitive(Primitive::Boolean),
+            &[JValue::from(artifact_id)],
+        );
+        env.delete_local_ref(artifact_id)?;
+        result?.z()
+    }
+}
+
+/// Refers to the cached methods of the `BlockchainData` class, the database
+/// accesses it provides and the `Blockchain` it exposes (`getBlockchain`).
+pub mod blockchain_data {
+    use super::*;
+    use jni::signature::Primitive;
+    use std::convert::TryFrom;
+
+    cache_methods! {
+        /// Returns cached `JMethodID` for `BlockchainData.getExecutingServiceData()`.
+        BLOCKCHAIN_DATA_GET_EXECUTING_SERVICE_DATA: (
+            BLOCKCHAIN_DATA_CLASS,
+            "getExecutingServiceData",
+            "()Lcom/exonum/binding/core/storage/database/Prefixed;",
+        ) => get_executing_service_data_id, try_get_executing_service_data_id;
+        /// Returns cached `JMethodID` for `BlockchainData.getBlockchain()`.
+        BLOCKCHAIN_DATA_GET_BLOCKCHAIN: (
+            BLOCKCHAIN_DATA_CLASS,
+            "getBlockchain",
+            "()Lcom/exonum/binding/core/blockchain/Blockchain;",
+        ) => get_blockchain_id, try_get_blockchain_id;
+        /// Returns cached `JMethodID` for `AbstractAccess.getAccessNativeHandle()`.
+        ABSTRACT_ACCESS_GET_ACCESS_NATIVE_HANDLE: (
+            ABSTRACT_ACCESS_CLASS,
+            "getAccessNativeHandle",
+            "()J",
+        ) => get_access_native_handle_id, try_get_access_native_handle_id;
+        /// Returns cached `JMethodID` for `AbstractAccess.canModify()`.
+        ABSTRACT_ACCESS_CAN_MODIFY: (
+            ABSTRACT_ACCESS_CLASS,
+            "canModify",
+            "()Z",
+        ) => can_modify_id, try_can_modify_id;
+        /// Returns cached `JMethodID` for `Blockchain.getHeight()`.
+        BLOCKCHAIN_GET_HEIGHT: (BLOCKCHAIN_CLASS, "getHeight", "()J")
+            => get_height_id, try_get_height_id;
+        /// Returns cached `JMethodID` for `Blockchain.getLastBlock()`.
+        BLOCKCHAIN_GET_LAST_BLOCK: (
+            BLOCKCHAIN_CLASS,
+            "getLastBlock",
+            "()Lcom/exonum/binding/core/blockchain/Block;",
+        ) => get_last_block_id, try_get_last_block_id;
+        /// Returns cached `JMethodID` for `Block.getBlockHash()`.
+        BLOCK_GET_BLOCK_HASH: (
+            BLOCK_CLASS,
+            "getBlockHash",
+            "()Lcom/exonum/binding/common/hash/HashCode;",
+        ) => get_block_hash_id, try_get_block_hash_id;
+        /// Returns cached `JMethodID` for `HashCode.asBytes()`.
+        HASH_CODE_AS_BYTES: (HASH_CODE_CLASS, "asBytes", "()[B")
+            => as_bytes_id, try_as_bytes_id;
+        /// Returns cached `JMethodID` for `Blockchain.getConsensusConfiguration()`.
+        BLOCKCHAIN_GET_CONSENSUS_CONFIGURATION: (
+            BLOCKCHAIN_CLASS,
+            "getConsensusConfiguration",
+            "()Lcom/exonum/messages/core/Blockchain$Config;",
+        ) => get_consensus_configuration_id, try_get_consensus_configuration_id;
+    }
+
+    /// Returns the height of the latest committed block of the given `Blockchain`.
+    ///
+    /// The Java heights are non-negative `long`s, so the valid range is
+    /// `0..=i64::MAX`; a negative height is reported as an error rather than wrapped.
+    /// Returns an error with a pending `RuntimeException` if the genesis block
+    /// is not committed yet.
+    pub fn current_height(env: &JNIEnv, blockchain: JObject) -> JniResult<u64> {
+        let height = env
+            .call_method_unchecked(
+                blockchain,
+                get_height_id(),
+                JavaType::Primitive(Primitive::Long),
+                &[],
+            )?
+            .j()?;
+        u64::try_from(height).map_err(|_| {
+            JniErrorKind::Msg(format!("Negative blockchain height: {}", height)).into()
+        })
+    }
+
+    /// Returns the hash of the latest committed block of the given `Blockchain`.
+    ///
+    /// Returns an error with a pending `IllegalStateException` if the genesis block
+    /// is not committed yet.
+    pub fn current_block_hash(env: &JNIEnv, blockchain: JObject) -> JniResult<Vec<u8>> {
+        let block = env
+            .call_method_unchecked(
+                blockchain,
+                get_last_block_id(),
+                JavaType::Object(BLOCK_CLASS.into()),
+                &[],
+            )?
+            .l()?;
+        let hash = block_hash(env, block);
+        env.delete_local_ref(block)?;
+        hash
+    }
+
+    /// Returns the hash of the given `Block`.
+    pub fn block_hash(env: &JNIEnv, block: JObject) -> JniResult<Vec<u8>> {
+        let hash = env
+            .call_method_unchecked(
+                block,
+                get_block_hash_id(),
+                JavaType::Object(HASH_CODE_CLASS.into()),
+                &[],
+            )?
+            .l()?;
+        let bytes = env
+            .call_method_unchecked(
+                hash,
+                as_bytes_id(),
+                JavaType::Array(Box::new(JavaType::Primitive(Primitive::Byte))),
+                &[],
+            )
+            .and_then(JValue::l);
+        env.delete_local_ref(hash)?;
+        let bytes = bytes?;
+        let result = env.convert_byte_array(bytes.into_inner());
+        env.delete_local_ref(bytes)?;
+        result
+    }
+
+    /// Error of `consensus_config`.
+    #[derive(Debug)]
+    pub enum ConsensusConfigError {
+        /// The configuration is not available yet, as the genesis block is not committed
+        /// (the Java code threw `IllegalStateException` with this message, cleared).
+        NotAvailable(String),
+        /// JNI error occurred during the call, possibly with a pending Java exception.
+        Jni(JniError),
+    }
+
+    impl fmt::Display for ConsensusConfigError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                ConsensusConfigError::NotAvailable(message) => {
+                    write!(f, "Consensus configuration is not available: {}", message)
+                }
+                ConsensusConfigError::Jni(e) => {
+                    write!(f, "Failed to get consensus configuration: {}", e)
+                }
+            }
+        }
+    }
+
+    impl Error for ConsensusConfigError {}
+
+    impl From<JniError> for ConsensusConfigError {
+        fn from(e: JniError) -> Self {
+            ConsensusConfigError::Jni(e)
+        }
+    }
+
+    impl From<ConsensusConfigError> for JniError {
+        fn from(e: ConsensusConfigError) -> Self {
+            match e {
+                ConsensusConfigError::NotAvailable(_) => JniErrorKind::Msg(e.to_string()).into(),
+                ConsensusConfigError::Jni(e) => e,
+            }
+        }
+    }
+
+    /// Returns the current consensus configuration of the network, serialized
+    /// `exonum.Config` message, from the given `Blockchain`.
+    ///
+    /// The configuration is available only after the genesis block is committed, e.g.,
+    /// not during the initialization of the services in the genesis block; the call
+    /// in such phases results in `NotAvailable` error.
+    pub fn consensus_config(
+        env: &JNIEnv,
+        blockchain: JObject,
+    ) -> Result<Vec<u8>, ConsensusConfigError> {
+        let config = env
+            .call_method_unchecked(
+                blockchain,
+                get_consensus_configuration_id(),
+                JavaType::Object("com/exonum/messages/core/Blockchain$Config".into()),
+                &[],
+            )
+            .and_then(JValue::l);
+        let config = match config {
+            Ok(config) => config,
+            Err(e) => return Err(not_available_or(env, e)),
+        };
+        let serialized = super::protobuf::serialize_message(env, config);
+        env.delete_local_ref(config)?;
+        Ok(serialized?)
+    }
+
+    /// Converts the pending `IllegalStateException` to `NotAvailable` error, clearing it.
+    /// Other errors (and exceptions) are kept as is.
+    fn not_available_or(env: &JNIEnv, error: JniError) -> ConsensusConfigError {
+        if !env.exception_check().unwrap_or(false) {
+            return error.into();
+        }
+        let exception = match env.exception_occurred() {
+            Ok(exception) => exception,
+            Err(_) => return error.into(),
+        };
+        let _ = env.exception_clear();
+        let kind = crate::utils::classify_exception(env, exception);
+        if kind == crate::utils::ExceptionKind::IllegalState {
+            let message = throwable::message(env, exception).ok().flatten();
+            ConsensusConfigError::NotAvailable(message.unwrap_or_default())
+        } else {
+            let _ = env.throw(exception);
+            error.into()
+        }
+    }
+}
+
+/// Refers to the cached methods of the `java.lang.Object` class.
+pub mod object {
+    use super::*;
+
+    /// Returns cached `JMethodID` for `java.lang.Object.getClass()`.
+    pub fn get_class_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { OBJECT_GET_CLASS })
+    }
+
+    /// Returns cached `JMethodID` for `java.lang.Object.toString()`.
+    pub fn to_string_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { OBJECT_TO_STRING })
+    }
+
+    /// Returns the fully-qualified name of the class of the given object
+    /// (e.g., `java.lang.String`), using the cached `getClass` and `Class.getName`.
+    ///
+    /// Returns an error for the null object. An exception thrown by the Java code
+    /// is cleared.
+    pub fn class_name(env: &JNIEnv, obj: JObject) -> JniResult<String> {
+        if obj.is_null() {
+            return Err(JniErrorKind::NullPtr("Object").into());
+        }
+        crate::utils::get_class_name(env, obj).map_err(|e| {
+            if env.exception_check().unwrap_or(false) {
+                let _ = env.exception_clear();
+            }
+            e
+        })
+    }
+
+    /// Same as `class_name`, but obtains the `JNIEnv` of the current thread with
+    /// `with_attached_thread`, for the callers holding an object (e.g., a `GlobalRef`)
+    /// but no env.
+    ///
+    /// Attaching a detached thread (and detaching it after the call) is much more costly
+    /// than the call itself, so the code on hot paths shall pass its env to `class_name`
+    /// instead. The object must be a global reference or a local reference of
+    /// the current thread.
+    ///
+    /// Panics if the cache is not initialized.
+    pub fn class_name_on_current_thread(obj: JObject) -> JniResult<String> {
+        with_attached_thread(|env| class_name(env, obj))
+    }
+}
+
+/// Classes of the service instances resolved with the class loaders of their artifacts,
+/// so that the first transaction of a newly added service does not incur the class loading
+/// latency.
+///
+/// The classes are tracked by the service instance and released when the service
+/// is stopped or the cache is reset.
+pub mod service_classes {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Identifier of the service instance the classes belong to.
+    pub type ServiceId = u32;
+
+    static SERVICE_CLASSES: Mutex<Option<HashMap<ServiceId, Vec<(String, CountedRef)>>>> =
+        const_mutex(None);
+
+    /// Resolves the given classes (in JNI form, e.g., `com/acme/Foo`) with the class
+    /// loader of the service artifact using `get_class_with_loader`, and caches them
+    /// for the service instance. Intended to be invoked right after the service
+    /// is added (`initiateAddingService`).
+    ///
+    /// Returns an error if any of the classes is not found, in which case none of them
+    /// is cached.
+    pub fn warm_service_classes(
+        env: &JNIEnv,
+        service_id: ServiceId,
+        class_names: &[String],
+        loader: JObject,
+    ) -> Result<(), CacheError> {
+        let classes = class_names
+            .iter()
+            .map(|name| get_class_with_loader(env, name, loader).map(|class| (name.clone(), class)))
+            .collect::<Result<Vec<_>, _>>()?;
+        SERVICE_CLASSES
+            .lock()
+            .get_or_insert_with(HashMap::new)
+            .entry(service_id)
+            .or_insert_with(Vec::new)
+            .extend(classes);
+        Ok(())
+    }
+
+    /// Returns the class of the service instance cached by `warm_service_classes`.
+    pub fn service_class(service_id: ServiceId, class_name: &str) -> Option<CountedRef> {
+        SERVICE_CLASSES.lock().as_ref().and_then(|services| {
+            services.get(&service_id).and_then(|classes| {
+                classes
+                    .iter()
+                    .find(|(name, _)| name == class_name)
+                    .map(|(_, class)| class.clone())
+            })
+        })
+    }
+
+    /// Drops the classes cached for the service instance, e.g., when it is stopped.
+    /// Returns the number of the released classes.
+    pub fn release_service_classes(service_id: ServiceId) -> usize {
+        // The references are released outside of the lock.
+        let classes = SERVICE_CLASSES
+            .lock()
+            .as_mut()
+            .and_then(|services| services.remove(&service_id));
+        classes.map_or(0, |classes| classes.len())
+    }
+
+    /// Drops the classes cached for all the service instances.
+    pub fn clear() {
+        let services = SERVICE_CLASSES.lock().take();
+        drop(services);
+    }
+}
+
+/// Registry of `ServiceRuntimeAdapter` instances, for the deployments running several
+/// adapters in one JVM (e.g., isolated test environments). The cached adapter methods
+/// are shared by all the instances, as they belong to the class.
+///
+/// The registered adapters are dropped when the cache is reset.
+pub mod adapters {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Identifier of the runtime an adapter serves, e.g., `JAVA_RUNTIME_ID`.
+    pub type RuntimeId = u32;
+
+    static ADAPTERS: Mutex<Option<HashMap<RuntimeId, CountedRef>>> = const_mutex(None);
+
+    /// Registers the adapter serving the runtime with the given id. Returns the adapter
+    /// previously registered with this id, if any.
+    pub fn register_adapter(id: RuntimeId, adapter: CountedRef) -> Option<CountedRef> {
+        ADAPTERS
+            .lock()
+            .get_or_insert_with(HashMap::new)
+            .insert(id, adapter)
+    }
+
+    /// Returns the adapter registered with the given id.
+    pub fn adapter(id: RuntimeId) -> Option<CountedRef> {
+        ADAPTERS
+            .lock()
+            .as_ref()
+            .and_then(|adapters| adapters.get(&id).cloned())
+    }
+
+    /// Removes the adapter registered with the given id, returning it.
+    pub fn unregister_adapter(id: RuntimeId) -> Option<CountedRef> {
+        ADAPTERS
+            .lock()
+            .as_mut()
+            .and_then(|adapters| adapters.remove(&id))
+    }
+
+    /// Drops all the registered adapters.
+    pub fn clear() {
+        // The references are released outside of the lock.
+        let adapters = ADAPTERS.lock().take();
+        drop(adapters);
+    }
+}
+
+/// Interned Java strings for the constant arguments passed to the Java code.
+pub mod strings {
+    use super::*;
+
+    /// Strings that are interned by `intern_string`: the names of the interfaces
+    /// of the transactions (the default interface and `exonum.Configure`).
+    ///
+    /// The set is fixed, so the intern table cannot grow from the strings coming
+    /// from the network (e.g., the interface names of incoming transactions).
+    pub const INTERNED: &[&str] = &["", "exonum.Configure"];
+
+    /// Returns the Java string with the given contents as a `GlobalRef`, creating it
+    /// on the first call. The strings in `INTERNED` are created once and held until
+    /// the cache is reset; a new string is created on each call for any other string.
+    /// The strings are counted as held by the native library while the returned reference
+    /// (or the interned one) is alive (see `ref_metrics`).
+    pub fn intern_string(env: &JNIEnv, s: &'static str) -> JniResult<CountedRef> {
+        if !INTERNED.contains(&s) {
+            debug!(
+                "Not interning string {:?}: it is not in the interned set",
+                s
+            );
+            return new_global_string(env, s);
+        }
+        let mut interned = INTERNED_STRINGS.lock();
+        if let Some((_, string)) = interned.iter().find(|(interned, _)| *interned == s) {
+            return Ok(string.clone());
+        }
+        let string = new_global_string(env, s)?;
+        interned.push((s, string.clone()));
+        Ok(string)
+    }
+
+    /// Returns the interned string equal to the given one, if any.
+    pub fn known_string(s: &str) -> Option<&'static str> {
+        INTERNED.iter().find(|&&interned| interned == s).copied()
+    }
+
+    /// Releases the interned strings.
+    pub(super) fn clear_interned() {
+        INTERNED_STRINGS.lock().clear();
+    }
+
+    fn new_global_string(env: &JNIEnv, s: &str) -> JniResult<CountedRef> {
+        let string = JObject::from(env.new_string(s)?);
+        let global_ref = ref_metrics::new_global_ref(env, string);
+        env.delete_local_ref(string)?;
+        global_ref
+    }
+}
+
+/// Refers to the cached methods of the `java.lang.Thread` class.
+pub mod thread {
+    use super::*;
+
+    /// Returns cached `JMethodID` for `java.lang.Thread.getContextClassLoader()`.
+    pub fn get_context_class_loader_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { THREAD_GET_CONTEXT_CLASS_LOADER })
+    }
+
+    /// Returns the context class loader of the current thread, which shall be used
+    /// to resolve the classes of the services deployed at runtime (see
+    /// `get_class_with_loader`). The returned loader is `null` if it is not set.
+    pub fn current_context_loader<'a>(env: &JNIEnv<'a>) -> JniResult<JObject<'a>> {
+        context_class_loader(
+            env,
+            static_methods::thread_current_thread_id(),
+            get_context_class_loader_id(),
+        )
+    }
+}
+
+/// Refers to the cached methods of the `java.lang.AutoCloseable` interface.
+pub mod auto_closeable {
+    use super::*;
+    use crate::utils::{describe_java_exception, describe_object, get_and_clear_java_exception};
+    use jni::{objects::JThrowable, signature::Primitive};
+
+    /// Returns cached `JMethodID` for `java.lang.AutoCloseable.close()`.
+    pub fn close_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { AUTO_CLOSEABLE_CLOSE })
+    }
+
+    /// Closes the given `AutoCloseable` object, logging and clearing any exception
+    /// thrown by `close()` instead of propagating it.
+    ///
+    /// If an exception is pending (i.e., the object is closed during the exception
+    /// propagation), it is kept pending, and the exception thrown by `close()`, if any,
+    /// is attached to it as suppressed instead, so that it neither masks the original
+    /// exception nor is lost.
+    ///
+    /// Intended to be used in `Drop` implementations, where errors cannot be returned.
+    pub fn close_quietly(env: &JNIEnv, obj: JObject) {
+        if obj.is_null() {
+            return;
+        }
+        let primary = if env.exception_check().unwrap_or(false) {
+            Some(get_and_clear_java_exception(env))
+        } else {
+            None
+        };
+        let result =
+            env.call_method_unchecked(obj, close_id(), JavaType::Primitive(Primitive::Void), &[]);
+        match (result, primary) {
+            (Ok(_), None) => {}
+            (Ok(_), Some(primary)) => rethrow(env, primary),
+            (Err(e), primary) => {
+                if !env.exception_check().unwrap_or(false) {
+                    warn!("Failed to close {}: {}", describe_object(env, obj), e);
+                    if let Some(primary) = primary {
+                        rethrow(env, primary);
+                    }
+                    return;
+                }
+                let secondary = get_and_clear_java_exception(env);
+                match primary {
+                    Some(primary) => {
+                        if let Err(e) =
+                            throwable::add_suppressed(env, primary.into(), secondary.into())
+                        {
+                            let _ = env.exception_clear();
+                            warn!(
+                                "Failed to close {}: {} (cannot be suppressed: {})",
+                                describe_object(env, obj),
+                                describe_java_exception(env, secondary),
+                                e
+                            );
+                        }
+                        rethrow(env, primary);
+                    }
+                    None => warn!(
+                        "Failed to close {}: {}",
+                        describe_object(env, obj),
+                        describe_java_exception(env, secondary)
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Makes the exception cleared by `close_quietly` pending again.
+    fn rethrow(env: &JNIEnv, exception: JObject) {
+        if let Err(e) = env.throw(JThrowable::from(exception)) {
+            error!("Failed to rethrow the pending exception: {}", e);
+        }
+    }
+}
+
+/// Refers to the cached methods of the `java.lang.Class` class.
+pub mod class {
+    use super::*;
+
+    /// Returns cached `JMethodID` for `java.lang.Class.getName()`.
+    pub fn get_name_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { CLASS_GET_NAME })
+    }
+
+    /// Returns cached `JMethodID` for `java.lang.Class.getSimpleName()`.
+    pub fn get_simple_name_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { CLASS_GET_SIMPLE_NAME })
+    }
+
+    /// Returns cached `JMethodID` for `java.lang.Class.isAssignableFrom()`.
+    pub fn is_assignable_from_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { CLASS_IS_ASSIGNABLE_FROM })
+    }
+}
+
+/// Refers to the cached methods of the `java.lang.Throwable` class.
+pub mod throwable {
+    use super::*;
+    use jni::{objects::JThrowable, signature::Primitive};
+
+    /// Returns cached `JMethodID` for `java.lang.Throwable.getMessage()`.
+    pub fn get_message_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { THROWABLE_GET_MESSAGE })
+    }
+
+    /// Returns cached `JMethodID` for `java.lang.Throwable.getLocalizedMessage()`.
+    pub fn get_localized_message_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { THROWABLE_GET_LOCALIZED_MESSAGE })
+    }
+
+    /// Returns cached `JMethodID` for `java.lang.Throwable.getCause()`.
+    pub fn get_cause_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { THROWABLE_GET_CAUSE })
+    }
+
+    /// Returns cached `JMethodID` for `java.lang.Throwable.getStackTrace()`.
+    pub fn get_stack_trace_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { THROWABLE_GET_STACK_TRACE })
+    }
+
+    /// Returns cached `JMethodID` for `java.lang.Throwable.addSuppressed()`.
+    pub fn add_suppressed_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { THROWABLE_ADD_SUPPRESSED })
+    }
+
+    /// Attaches the secondary throwable (e.g., a failure of the cleanup) to the primary one
+    /// as suppressed, so that it is reported along with the primary one instead of being lost.
+    ///
+    /// Panics if `primary` is null.
+    pub fn add_suppressed(
+        env: &JNIEnv,
+        primary: JThrowable,
+        secondary: JThrowable,
+    ) -> JniResult<()> {
+        let primary: JObject = primary.into();
+        assert!(!primary.is_null(), "Invalid throwable argument");
+        env.call_method_unchecked(
+            primary,
+            add_suppressed_id(),
+            JavaType::Primitive(Primitive::Void),
+            &[JValue::from(JObject::from(secondary))],
+        )?
+        .v()
+    }
+
+    /// Returns the message of the throwable, or `None` if it is null.
+    ///
+    /// Panics if `throwable` is null.
+    pub fn message(env: &JNIEnv, throwable: JThrowable) -> JniResult<Option<String>> {
+        crate::utils::get_exception_message(env, throwable.into())
+    }
+
+    /// Returns the cause of the throwable, or `None` if it is null.
+    ///
+    /// Panics if `throwable` is null.
+    pub fn cause<'a>(
+        env: &JNIEnv<'a>,
+        throwable: JThrowable<'a>,
+    ) -> JniResult<Option<JThrowable<'a>>> {
+        let throwable: JObject = throwable.into();
+        assert!(!throwable.is_null(), "Invalid throwable argument");
+        let cause = env
+            .call_method_unchecked(
+                throwable,
+                get_cause_id(),
+                JavaType::Object("java/lang/Throwable".into()),
+                &[],
+            )?
+            .l()?;
+        if cause.is_null() {
+            Ok(None)
+        } else {
+            Ok(Some(JThrowable::from(cause)))
+        }
+    }
+}
+
+/// Refers to the cached methods of the `java.lang.StackTraceElement` class.
+pub mod stack_trace_element {
+    use super::*;
+
+    /// Returns cached `JMethodID` for `java.lang.StackTraceElement.toString()`.
+    pub fn to_string_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { STACK_TRACE_ELEMENT_TO_STRING })
+    }
+}
+
+/// Refers to the cached methods of the `com.exonum.binding.core.transaction.ExecutionException` class.
+pub mod execution_exception {
+    use super::*;
+    use jni::{
+        objects::{JObject, JThrowable, JValue},
+        signature::Primitive,
+    };
+
+    /// Returns cached `JMethodID` for `ExecutionException.getErrorCode()`.
+    pub fn get_error_code_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { EXECUTION_EXCEPTION_GET_ERROR_CODE })
+    }
+
+    /// Returns cached `JMethodID` for `ExecutionException(byte, String)` constructor.
+    pub fn constructor_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { EXECUTION_EXCEPTION_CONSTRUCTOR })
+    }
 
-    catch_unwind(|| {
-        init_cache(&env);
-        JNI_VERSION_1_8
-    })
-    .unwrap_or(INVALID_JNI_VERSION)
-}
+    /// Creates a new `ExecutionException` with the given error code and description
+    /// using the cached class and constructor.
+    pub fn new_execution_exception<'a>(
+        env: &JNIEnv<'a>,
+        error_code: i8,
+        description: &str,
+    ) -> JniResult<JThrowable<'a>> {
+        let description = JObject::from(env.new_string(description)?);
+        env.new_object_unchecked(
+            &classes_refs::execution_exception(),
+            constructor_id(),
+            &[JValue::from(error_code), JValue::from(description)],
+        )
+        .map(JThrowable::from)
+    }
 
-/// Initializes JNI cache considering synchronization
-pub fn init_cache(env: &JNIEnv) {
-    INIT.call_once(|| unsafe { cache_methods(env) });
-}
+    /// Returns the error code of the given `ExecutionException` using the cached
+    /// `getErrorCode` method.
+    ///
+    /// Returns an error if the exception object is null or `getErrorCode` throws.
+    pub fn read_error_code(env: &JNIEnv, exception: JObject) -> JniResult<i8> {
+        if exception.is_null() {
+            return Err(JniErrorKind::NullPtr("ExecutionException").into());
+        }
+        env.call_method_unchecked(
+            exception,
+            get_error_code_id(),
+            JavaType::Primitive(Primitive::Byte),
+            &[],
+        )?
+        .b()
+    }
 
-/// Caches all required classes and methods ids.
-unsafe fn cache_methods(env: &JNIEnv) {
-    OBJECT_GET_CLASS = get_method_id(&env, "java/lang/Object", "getClass", "()Ljava/lang/Class;");
-    CLASS_GET_NAME = get_method_id(&env, "java/lang/Class", "getName", "()Ljava/lang/String;");
-    THROWABLE_GET_MESSAGE = get_method_id(
-        &env,
-        "java/lang/Throwable",
-        "getMessage",
-        "()Ljava/lang/String;",
-    );
-    THROWABLE_GET_CAUSE = get_method_id(
-        &env,
-        "java/lang/Throwable",
-        "getCause",
-        "()Ljava/lang/Throwable;",
-    );
-    EXECUTION_EXCEPTION_GET_ERROR_CODE = get_method_id(
-        &env,
-        "com/exonum/binding/core/service/ExecutionException",
-        "getErrorCode",
-        "()B",
-    );
-    RUNTIME_ADAPTER_INITIALIZE =
-        get_method_id(&env, SERVICE_RUNTIME_ADAPTER_CLASS, "initialize", "(J)V");
-    RUNTIME_ADAPTER_DEPLOY_ARTIFACT = get_method_id(
-        &env,
-        SERVICE_RUNTIME_ADAPTER_CLASS,
-        "deployArtifact",
-        "([B[B)V",
-    );
-    RUNTIME_ADAPTER_IS_ARTIFACT_DEPLOYED = get_method_id(
-        &env,
-        SERVICE_RUNTIME_ADAPTER_CLASS,
-        "isArtifactDeployed",
-        "([B)Z",
-    );
-    RUNTIME_ADAPTER_INITIATE_ADDING_SERVICE = get_method_id(
-        &env,
-        SERVICE_RUNTIME_ADAPTER_CLASS,
-        "initiateAddingService",
-        "(J[B[B)V",
-    );
-    RUNTIME_ADAPTER_INITIATE_RESUMING_SERICE = get_method_id(
-        &env,
-        SERVICE_RUNTIME_ADAPTER_CLASS,
-        "initiateResumingService",
-        "(J[B[B)V",
-    );
-    RUNTIME_ADAPTER_UPDATE_SERVICE_STATUS = get_method_id(
-        &env,
-        SERVICE_RUNTIME_ADAPTER_CLASS,
-        "updateServiceStatus",
-        "([B[B)V",
-    );
-    RUNTIME_ADAPTER_EXECUTE_TX = get_method_id(
-        &env,
-        SERVICE_RUNTIME_ADAPTER_CLASS,
-        "executeTransaction",
-        "(ILjava/lang/String;I[BJI[B[B)V",
-    );
-    RUNTIME_ADAPTER_BEFORE_TRANSACTIONS = get_method_id(
-        &env,
-        SERVICE_RUNTIME_ADAPTER_CLASS,
-        "beforeTransactions",
-        "(IJ)V",
-    );
-    RUNTIME_ADAPTER_AFTER_TRANSACTIONS = get_method_id(
-        &env,
-        SERVICE_RUNTIME_ADAPTER_CLASS,
-        "afterTransactions",
-        "(IJ)V",
-    );
-    RUNTIME_ADAPTER_AFTER_COMMIT =
-        get_method_id(&env, SERVICE_RUNTIME_ADAPTER_CLASS, "afterCommit", "(JIJ)V");
-    RUNTIME_ADAPTER_SHUTDOWN =
-        get_method_id(&env, SERVICE_RUNTIME_ADAPTER_CLASS, "shutdown", "()V");
-
-    JAVA_LANG_ERROR = get_class(env, "java/lang/Error");
-    JAVA_LANG_RUNTIME_EXCEPTION = get_class(env, "java/lang/RuntimeException");
-    JAVA_LANG_ILLEGAL_ARGUMENT_EXCEPTION = get_class(env, "java/lang/IllegalArgumentException");
-    EXECUTION_EXCEPTION = get_class(env, "com/exonum/binding/core/service/ExecutionException");
-    UNEXPECTED_EXECUTION_EXCEPTION = get_class(
-        env,
-        "com/exonum/binding/core/runtime/UnexpectedExecutionException",
-    );
+    /// Error code and description of an `ExecutionException`.
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct ExecutionError {
+        /// The error code, see `ExecutionException.getErrorCode`.
+        pub code: i8,
+        /// The message of the exception, or an empty string if it is null.
+        pub description: String,
+    }
 
-    debug!("Done caching references to Java classes and methods.");
+    /// Reads the error code and the description of the given `ExecutionException`.
+    ///
+    /// Returns an error if the object is null or not an `ExecutionException` (its class
+    /// name is included in the error), or if the Java code throws, clearing the exception.
+    pub fn to_execution_error(env: &JNIEnv, exception: JObject) -> JniResult<ExecutionError> {
+        if exception.is_null() {
+            return Err(JniErrorKind::NullPtr("ExecutionException").into());
+        }
+        let read = || -> JniResult<ExecutionError> {
+            if !crate::utils::is_instance_of_cached(
+                env,
+                exception,
+                &classes_refs::execution_exception(),
+            ) {
+                let class_name = object::class_name(env, exception)?;
+                return Err(JniErrorKind::Msg(format!(
+                    "Expected ExecutionException, but was {}",
+                    class_name
+                ))
+                .into());
+            }
+            let code = read_error_code(env, exception)?;
+            let description = throwable::message(env, exception.into())?.unwrap_or_default();
+            Ok(ExecutionError { code, description })
+        };
+        read().map_err(|e| {
+            if env.exception_check().unwrap_or(false) {
+                let _ = env.exception_clear();
+            }
+            e
+        })
+    }
 }
 
-/// Produces `JMethodID` for a particular method dealing with its lifetime.
-///
-/// Always returns `Some(method_id)`, panics if method not found.
-fn get_method_id(env: &JNIEnv, class: &str, name: &str, sig: &str) -> Option<JMethodID<'static>> {
-    let method_id = env
-        .get_method_id(class, name, sig)
-        // we need this line to erase lifetime in order to save underlying raw pointer in static
-        .map(|mid| mid.into_inner().into())
-        .unwrap_or_else(|_| {
-            panic!(
-                "Method {} with signature {} of class {} not found",
-                name, sig, class
-            )
-        });
-    Some(method_id)
-}
+/// Refers to the cached methods of the
+/// `com.exonum.binding.core.runtime.UnexpectedExecutionException` class.
+pub mod unexpected_execution_exception {
+    use super::*;
+    use jni::objects::{JObject, JThrowable, JValue};
 
-/// Returns cached class reference.
-///
-/// Always returns Some(class_ref), panics if class not found.
-fn get_class(env: &JNIEnv, class: &str) -> Option<GlobalRef> {
-    let class = env
-        .find_class(class)
-        .unwrap_or_else(|_| panic!("Class {} not found", class));
-    Some(env.new_global_ref(class).unwrap())
-}
+    /// Returns cached `JMethodID` for `UnexpectedExecutionException(Throwable)` constructor.
+    pub fn constructor_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { UNEXPECTED_EXECUTION_EXCEPTION_CONSTRUCTOR })
+    }
 
-fn check_cache_initialized() {
-    if !INIT.state().done() {
-        panic!("JNI cache is not initialized")
+    /// Throws a new `UnexpectedExecutionException` caused by a `RuntimeException`
+    /// with the given message.
+    ///
+    /// Allows to signal unexpected native failures during transaction execution
+    /// to the Java side instead of panicking across the JNI boundary.
+    pub fn throw_unexpected_execution_exception(env: &JNIEnv, message: &str) -> JniResult<()> {
+        // UnexpectedExecutionException requires a non-null cause.
+        let message = JObject::from(env.new_string(message)?);
+        let cause = env.new_object(
+            &classes_refs::java_lang_runtime_exception(),
+            "(Ljava/lang/String;)V",
+            &[JValue::from(message)],
+        )?;
+        let exception = env.new_object_unchecked(
+            &classes_refs::unexpected_execution_exception(),
+            constructor_id(),
+            &[JValue::from(cause)],
+        )?;
+        env.throw(JThrowable::from(exception))
     }
 }
 
-/// Refers to the cached methods of the `ServiceRuntimeAdapter` class.
-pub mod runtime_adapter {
+/// Refers to the cached static methods of various classes.
+///
+/// Static methods must be invoked on the corresponding class from `classes_refs`.
+pub mod static_methods {
     use super::*;
 
-    /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.initialize()`.
-    pub fn initialize_id() -> JMethodID<'static> {
+    /// Returns cached `JStaticMethodID` for `java.lang.Class.forName()` accepting a class loader.
+    pub fn class_for_name_id() -> JStaticMethodID<'static> {
+        check_cache_initialized();
+        unsafe { CLASS_FOR_NAME.unwrap() }
+    }
+
+    /// Returns cached `JStaticMethodID` for `java.lang.Thread.currentThread()`.
+    pub fn thread_current_thread_id() -> JStaticMethodID<'static> {
         check_cache_initialized();
-        unsafe { RUNTIME_ADAPTER_INITIALIZE.unwrap() }
+        unsafe { THREAD_CURRENT_THREAD.unwrap() }
     }
 
-    /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.deployArtifact()`.
-    pub fn deploy_artifact_id() -> JMethodID<'static> {
+    /// Returns cached `JStaticMethodID` for `KeyPair.newInstance(byte[], byte[])`.
+    pub fn key_pair_new_instance_id() -> JStaticMethodID<'static> {
         check_cache_initialized();
-        unsafe { RUNTIME_ADAPTER_DEPLOY_ARTIFACT.unwrap() }
+        unsafe { KEY_PAIR_NEW_INSTANCE.unwrap() }
     }
 
-    /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.isArtifactDeployed()`.
-    pub fn is_artifact_deployed_id() -> JMethodID<'static> {
+    /// Returns cached `JStaticMethodID` for `java.lang.System.nanoTime()`.
+    pub fn system_nano_time_id() -> JStaticMethodID<'static> {
         check_cache_initialized();
-        unsafe { RUNTIME_ADAPTER_IS_ARTIFACT_DEPLOYED.unwrap() }
+        unsafe { SYSTEM_NANO_TIME.unwrap() }
     }
 
-    /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.initiateAddingService()`.
-    pub fn initiate_adding_service_id() -> JMethodID<'static> {
+    /// Returns cached `JStaticMethodID` for `java.lang.Integer.valueOf(int)`.
+    pub fn integer_value_of_id() -> JStaticMethodID<'static> {
         check_cache_initialized();
-        unsafe { RUNTIME_ADAPTER_INITIATE_ADDING_SERVICE.unwrap() }
+        unsafe { INTEGER_VALUE_OF.unwrap() }
     }
 
-    /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.initiateResumingService()`.
-    pub fn initiate_resuming_service_id() -> JMethodID<'static> {
+    /// Returns cached `JStaticMethodID` for `java.lang.Long.valueOf(long)`.
+    pub fn long_value_of_id() -> JStaticMethodID<'static> {
         check_cache_initialized();
-        unsafe { RUNTIME_ADAPTER_INITIATE_RESUMING_SERICE.unwrap() }
+        unsafe { LONG_VALUE_OF.unwrap() }
     }
+}
+
+/// Refers to the cached methods serializing the protobuf messages passed between Java and
+/// the native code (e.g., `ExecutionError` of the core).
+pub mod protobuf {
+    use super::*;
+    use jni::signature::Primitive;
 
-    /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.updateServiceStatus()`.
-    pub fn update_service_status_id() -> JMethodID<'static> {
+    /// Returns cached `JMethodID` for `com.google.protobuf.MessageLite.toByteArray()`.
+    pub fn to_byte_array_id() -> JMethodID<'static> {
         check_cache_initialized();
-        unsafe { RUNTIME_ADAPTER_UPDATE_SERVICE_STATUS.unwrap() }
+        cached_method_id(unsafe { MESSAGE_LITE_TO_BYTE_ARRAY })
+    }
+
+    /// Serializes the given protobuf message (e.g., `ExecutionError`) to bytes.
+    ///
+    /// Returns an error for the null object.
+    pub fn serialize_message(env: &JNIEnv, message: JObject) -> JniResult<Vec<u8>> {
+        if message.is_null() {
+            return Err(JniErrorKind::NullPtr("MessageLite").into());
+        }
+        let bytes = env
+            .call_method_unchecked(
+                message,
+                to_byte_array_id(),
+                JavaType::Array(Box::new(JavaType::Primitive(Primitive::Byte))),
+                &[],
+            )?
+            .l()?;
+        let serialized = env.convert_byte_array(bytes.into_inner());
+        env.delete_local_ref(bytes)?;
+        serialized
+    }
+
+    /// Serializes the given `com.exonum.messages.core.runtime.Errors.ExecutionError`
+    /// to bytes, so it can be decoded as `exonum::runtime::ExecutionError`.
+    pub fn serialize_execution_error(env: &JNIEnv, err: JObject) -> JniResult<Vec<u8>> {
+        serialize_message(env, err)
     }
+}
+
+/// Refers to the cached methods of `java.util.List` and `java.util.ArrayList`, used to pass
+/// the collections of results to Java.
+pub mod collections {
+    use super::*;
+    use jni::signature::Primitive;
 
-    /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.executeTransaction()`.
-    pub fn execute_tx_id() -> JMethodID<'static> {
+    /// Returns cached `JMethodID` for `java.util.ArrayList(int)` constructor.
+    pub fn array_list_constructor_id() -> JMethodID<'static> {
         check_cache_initialized();
-        unsafe { RUNTIME_ADAPTER_EXECUTE_TX.unwrap() }
+        cached_method_id(unsafe { ARRAY_LIST_CONSTRUCTOR })
     }
 
-    /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.beforeTransactions()`.
-    pub fn before_transactions_id() -> JMethodID<'static> {
+    /// Returns cached `JMethodID` for `java.util.List.add(Object)`.
+    pub fn list_add_id() -> JMethodID<'static> {
         check_cache_initialized();
-        unsafe { RUNTIME_ADAPTER_BEFORE_TRANSACTIONS.unwrap() }
+        cached_method_id(unsafe { LIST_ADD })
     }
 
-    /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.afterTransactions()`.
-    pub fn after_transactions_id() -> JMethodID<'static> {
+    /// Returns cached `JMethodID` for `java.util.List.size()`.
+    pub fn list_size_id() -> JMethodID<'static> {
         check_cache_initialized();
-        unsafe { RUNTIME_ADAPTER_AFTER_TRANSACTIONS.unwrap() }
+        cached_method_id(unsafe { LIST_SIZE })
+    }
+
+    /// Creates an empty `ArrayList` with the given initial capacity.
+    pub fn new_array_list<'a>(env: &JNIEnv<'a>, capacity: i32) -> JniResult<JObject<'a>> {
+        env.new_object_unchecked(
+            &classes_refs::java_util_array_list(),
+            array_list_constructor_id(),
+            &[JValue::from(capacity)],
+        )
+    }
+
+    /// Appends the element to the list.
+    ///
+    /// Returns an error if the list is not modified (which `ArrayList` never does).
+    pub fn list_add(env: &JNIEnv, list: JObject, element: JObject) -> JniResult<()> {
+        let modified = env
+            .call_method_unchecked(
+                list,
+                list_add_id(),
+                JavaType::Primitive(Primitive::Boolean),
+                &[JValue::from(element)],
+            )?
+            .z()?;
+        if modified {
+            Ok(())
+        } else {
+            Err(JniErrorKind::Msg("List.add did not modify the list".to_owned()).into())
+        }
     }
 
-    /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.afterCommit()`.
-    pub fn after_commit_id() -> JMethodID<'static> {
+    /// Appends the elements to the list, deleting the local reference to each element
+    /// once it is added, so that large collections do not overflow the local reference table.
+    pub fn list_add_all<'a, I>(env: &JNIEnv<'a>, list: JObject, elements: I) -> JniResult<()>
+    where
+        I: IntoIterator<Item = JObject<'a>>,
+    {
+        for element in elements {
+            let added = list_add(env, list, element);
+            env.delete_local_ref(element)?;
+            added?;
+        }
+        Ok(())
+    }
+
+    /// Returns the size of the list.
+    pub fn list_size(env: &JNIEnv, list: JObject) -> JniResult<i32> {
+        env.call_method_unchecked(
+            list,
+            list_size_id(),
+            JavaType::Primitive(Primitive::Int),
+            &[],
+        )?
+        .i()
+    }
+}
+
+/// Refers to the cached methods of the `java.util.Map.Entry` interface, used to iterate
+/// the maps passed from Java (e.g., proof map entries).
+pub mod map_entry {
+    use super::*;
+
+    /// Returns cached `JMethodID` for `java.util.Map.Entry.getKey()`.
+    pub fn get_key_id() -> JMethodID<'static> {
         check_cache_initialized();
-        unsafe { RUNTIME_ADAPTER_AFTER_COMMIT.unwrap() }
+        cached_method_id(unsafe { MAP_ENTRY_GET_KEY })
     }
 
-    /// Returns cached `JMethodID` for `ServiceRuntimeAdapter.shutdown()`.
-    pub fn shutdown_id() -> JMethodID<'static> {
+    /// Returns cached `JMethodID` for `java.util.Map.Entry.getValue()`.
+    pub fn get_value_id() -> JMethodID<'static> {
         check_cache_initialized();
-        unsafe { RUNTIME_ADAPTER_SHUTDOWN.unwrap() }
+        cached_method_id(unsafe { MAP_ENTRY_GET_VALUE })
+    }
+
+    /// Returns the key and the value of the given map entry.
+    ///
+    /// Returns an error for the null entry.
+    pub fn read_entry<'a>(
+        env: &JNIEnv<'a>,
+        entry: JObject<'a>,
+    ) -> JniResult<(JObject<'a>, JObject<'a>)> {
+        if entry.is_null() {
+            return Err(JniErrorKind::NullPtr("Map.Entry").into());
+        }
+        let object_type = || JavaType::Object("java/lang/Object".into());
+        let key = env
+            .call_method_unchecked(entry, get_key_id(), object_type(), &[])?
+            .l()?;
+        let value = env
+            .call_method_unchecked(entry, get_value_id(), object_type(), &[])?
+            .l()?;
+        Ok((key, value))
     }
 }
 
-/// Refers to the cached methods of the `java.lang.Object` class.
-pub mod object {
+/// Provides access to `java.lang.CharSequence` methods, so that the native code accepts
+/// any `CharSequence` (`String`, `StringBuilder`, etc.) where a string is expected.
+pub mod char_sequence {
     use super::*;
+    use jni::signature::Primitive;
 
-    /// Returns cached `JMethodID` for `java.lang.Object.getClass()`.
-    pub fn get_class_id() -> JMethodID<'static> {
+    /// Returns cached `JMethodID` for `java.lang.CharSequence.length()`.
+    pub fn length_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { CHAR_SEQUENCE_LENGTH })
+    }
+
+    /// Returns cached `JMethodID` for `java.lang.CharSequence.toString()`.
+    pub fn to_string_id() -> JMethodID<'static> {
         check_cache_initialized();
-        unsafe { OBJECT_GET_CLASS.unwrap() }
+        cached_method_id(unsafe { CHAR_SEQUENCE_TO_STRING })
+    }
+
+    /// Returns the number of UTF-16 code units in the given `CharSequence`.
+    ///
+    /// Returns an error for the null sequence.
+    pub fn length(env: &JNIEnv, cs: JObject) -> JniResult<i32> {
+        if cs.is_null() {
+            return Err(JniErrorKind::NullPtr("CharSequence").into());
+        }
+        env.call_method_unchecked(cs, length_id(), JavaType::Primitive(Primitive::Int), &[])?
+            .i()
+    }
+
+    /// Converts the given `CharSequence` to a Rust string.
+    ///
+    /// Returns an error for the null sequence.
+    pub fn to_rust_string(env: &JNIEnv, cs: JObject) -> JniResult<String> {
+        if cs.is_null() {
+            return Err(JniErrorKind::NullPtr("CharSequence").into());
+        }
+        let string = env
+            .call_method_unchecked(
+                cs,
+                to_string_id(),
+                JavaType::Object("java/lang/String".into()),
+                &[],
+            )?
+            .l()?;
+        let result = convert_to_string(env, string);
+        env.delete_local_ref(string)?;
+        result
     }
 }
 
-/// Refers to the cached methods of the `java.lang.Class` class.
-pub mod class {
+/// Provides access to `java.lang.reflect.Method` methods, so that the errors of reflective
+/// invocations can name the method that failed.
+pub mod reflect {
     use super::*;
 
-    /// Returns cached `JMethodID` for `java.lang.Class.getName()`.
+    /// Returns cached `JMethodID` for `java.lang.reflect.Method.getName()`.
     pub fn get_name_id() -> JMethodID<'static> {
         check_cache_initialized();
-        unsafe { CLASS_GET_NAME.unwrap() }
+        cached_method_id(unsafe { METHOD_GET_NAME })
+    }
+
+    /// Returns cached `JMethodID` for `java.lang.reflect.Method.getParameterTypes()`.
+    pub fn get_parameter_types_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { METHOD_GET_PARAMETER_TYPES })
+    }
+
+    /// Returns the name of the given reflected method.
+    ///
+    /// Returns an error for the null method.
+    pub fn method_name(env: &JNIEnv, method: JObject) -> JniResult<String> {
+        if method.is_null() {
+            return Err(JniErrorKind::NullPtr("Method").into());
+        }
+        let name = env
+            .call_method_unchecked(
+                method,
+                get_name_id(),
+                JavaType::Object("java/lang/String".into()),
+                &[],
+            )?
+            .l()?;
+        let result = convert_to_string(env, name);
+        env.delete_local_ref(name)?;
+        result
+    }
+
+    /// Returns the fully-qualified names of the parameter types of the given reflected
+    /// method, in the order of declaration.
+    ///
+    /// Returns an error for the null method.
+    pub fn parameter_type_names(env: &JNIEnv, method: JObject) -> JniResult<Vec<String>> {
+        if method.is_null() {
+            return Err(JniErrorKind::NullPtr("Method").into());
+        }
+        let types = env
+            .call_method_unchecked(
+                method,
+                get_parameter_types_id(),
+                JavaType::Array(Box::new(JavaType::Object(JAVA_LANG_CLASS_NAME.into()))),
+                &[],
+            )?
+            .l()?;
+        let len = env.get_array_length(types.into_inner())?;
+        let names = (0..len)
+            .map(|i| {
+                let class = env.get_object_array_element(types.into_inner(), i)?;
+                let name = env
+                    .call_method_unchecked(
+                        class,
+                        class::get_name_id(),
+                        JavaType::Object("java/lang/String".into()),
+                        &[],
+                    )
+                    .and_then(JValue::l);
+                env.delete_local_ref(class)?;
+                let name = name?;
+                let result = convert_to_string(env, name);
+                env.delete_local_ref(name)?;
+                result
+            })
+            .collect();
+        env.delete_local_ref(types)?;
+        names
+    }
+
+    /// Describes the given reflected method as its name followed by the parameter types,
+    /// e.g., `putEntry(java.lang.String, long)`.
+    ///
+    /// Returns an error for the null method.
+    pub fn describe_method(env: &JNIEnv, method: JObject) -> JniResult<String> {
+        let name = method_name(env, method)?;
+        let parameter_types = parameter_type_names(env, method)?;
+        Ok(format!("{}({})", name, parameter_types.join(", ")))
     }
 }
 
-/// Refers to the cached methods of the `java.lang.Throwable` class.
-pub mod throwable {
+/// Forwards the records of `log` crate to log4j, the logging framework of the Java code,
+/// so that the logs of the native library and the services appear in the same place.
+///
+/// Each record is formatted and passed to Java with a JNI call (attaching the thread
+/// if needed), which is orders of magnitude slower than the native loggers. Set
+/// the maximum level accordingly: `Debug` and `Trace` may slow down the node.
+#[cfg(feature = "java-logging")]
+pub mod log_bridge {
     use super::*;
+    use jni::signature::Primitive;
+    use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
 
-    /// Returns cached `JMethodID` for `java.lang.Throwable.getMessage()`.
-    pub fn get_message_id() -> JMethodID<'static> {
+    const LOG_MANAGER_CLASS: &str = "org/apache/logging/log4j/LogManager";
+    const LOGGER_CLASS: &str = "org/apache/logging/log4j/Logger";
+    /// The names of `Logger` methods for each `Level`, from `Error` to `Trace`.
+    const LEVEL_METHODS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+    static mut LOG_MANAGER_GET_LOGGER: Option<JStaticMethodID> = None;
+    static mut LOGGER_LEVEL_METHODS: [Option<JMethodID>; 5] = [None; 5];
+    static mut LOG_MANAGER: Option<CountedRef> = None;
+    /// Java loggers by the target of the record. The targets are the module paths
+    /// (or the explicit targets) of the native code, so the set is bounded.
+    static LOGGERS: Mutex<Vec<(String, CountedRef)>> = const_mutex(Vec::new());
+
+    thread_local! {
+        /// Whether a record is being forwarded on this thread, see `JavaLogger::log`.
+        static FORWARDING: Cell<bool> = Cell::new(false);
+    }
+
+    /// The methods of log4j resolved by `lookup`.
+    pub(super) struct LogBridgeIds {
+        get_logger: JStaticMethodID<'static>,
+        level_methods: [Option<JMethodID<'static>>; 5],
+        log_manager: CountedRef,
+    }
+
+    /// Resolves the methods of log4j. The ids are cached by `commit`.
+    pub(super) fn lookup(env: &JNIEnv) -> Result<LogBridgeIds, CacheError> {
+        let get_logger = get_static_method_id(
+            env,
+            LOG_MANAGER_CLASS,
+            "getLogger",
+            "(Ljava/lang/String;)Lorg/apache/logging/log4j/Logger;",
+        )?;
+        let mut level_methods = [None; 5];
+        for (method_id, name) in level_methods.iter_mut().zip(&LEVEL_METHODS) {
+            *method_id = Some(get_method_id(
+                env,
+                LOGGER_CLASS,
+                name,
+                "(Ljava/lang/String;)V",
+            )?);
+        }
+        let log_manager = get_class(env, LOG_MANAGER_CLASS)?;
+        Ok(LogBridgeIds {
+            get_logger,
+            level_methods,
+            log_manager,
+        })
+    }
+
+    /// Caches the methods of log4j resolved by `lookup`.
+    pub(super) unsafe fn commit(ids: LogBridgeIds) {
+        LOG_MANAGER_GET_LOGGER = Some(ids.get_logger);
+        LOGGER_LEVEL_METHODS = ids.level_methods;
+        LOG_MANAGER = Some(ids.log_manager);
+    }
+
+    /// Returns `true` if none of the methods of log4j is cached.
+    pub(super) unsafe fn is_clear() -> bool {
+        LOG_MANAGER_GET_LOGGER.is_none()
+            && LOGGER_LEVEL_METHODS.iter().all(Option::is_none)
+            && LOG_MANAGER.is_none()
+    }
+
+    /// Resets the cached methods and releases the loggers.
+    pub(super) unsafe fn clear() {
+        LOG_MANAGER_GET_LOGGER = None;
+        LOGGER_LEVEL_METHODS = [None; 5];
+        LOGGERS.lock().clear();
+        LOG_MANAGER = None;
+    }
+
+    /// Sets the cached method ids to the given sentinels.
+    #[cfg(feature = "mock-cache")]
+    pub(super) unsafe fn mock(
+        method_id: JMethodID<'static>,
+        static_method_id: JStaticMethodID<'static>,
+    ) {
+        LOG_MANAGER_GET_LOGGER = Some(static_method_id);
+        LOGGER_LEVEL_METHODS = [Some(method_id); 5];
+    }
+
+    /// Returns cached `JStaticMethodID` for `LogManager.getLogger(String)`.
+    pub fn get_logger_id() -> JStaticMethodID<'static> {
         check_cache_initialized();
-        unsafe { THROWABLE_GET_MESSAGE.unwrap() }
+        unsafe { LOG_MANAGER_GET_LOGGER.unwrap() }
     }
 
-    /// Returns cached `JMethodID` for `java.lang.Throwable.getCause()`.
-    pub fn get_cause_id() -> JMethodID<'static> {
+    /// Returns cached `JMethodID` for the `Logger` method logging a message
+    /// at the given level (e.g., `Logger.warn(String)`).
+    pub fn level_method_id(level: Level) -> JMethodID<'static> {
         check_cache_initialized();
-        unsafe { THROWABLE_GET_CAUSE.unwrap() }
+        cached_method_id(unsafe { LOGGER_LEVEL_METHODS[level as usize - 1] })
+    }
+
+    /// Installs `JavaLogger` as the logger of `log` crate with the given maximum level.
+    ///
+    /// Returns an error if a logger is already installed.
+    pub fn init(max_level: LevelFilter) -> Result<(), SetLoggerError> {
+        static LOGGER: JavaLogger = JavaLogger;
+        log::set_logger(&LOGGER)?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+
+    /// Forwards the records to log4j logger named after the target of the record
+    /// (with `::` replaced with `.`, e.g., `java_bindings.utils.jni_cache`).
+    ///
+    /// The records are dropped while the cache is not initialized, when the thread
+    /// has a pending Java exception (no Java code may be called), and when logged
+    /// by the forwarding itself.
+    #[derive(Debug)]
+    pub struct JavaLogger;
+
+    impl Log for JavaLogger {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            is_cache_initialized()
+        }
+
+        fn log(&self, record: &Record) {
+            if !self.enabled(record.metadata()) || FORWARDING.with(Cell::get) {
+                return;
+            }
+            let _guard = ForwardingGuard::new();
+            let message = record.args().to_string();
+            with_attached_thread(|env| {
+                if env.exception_check().unwrap_or(true) {
+                    return;
+                }
+                if forward(env, record.target(), record.level(), &message).is_err() {
+                    let _ = env.exception_clear();
+                }
+            });
+        }
+
+        fn flush(&self) {}
+    }
+
+    /// Marks the current thread as forwarding a record until dropped (including unwinding).
+    struct ForwardingGuard;
+
+    impl ForwardingGuard {
+        fn new() -> Self {
+            FORWARDING.with(|forwarding| forwarding.set(true));
+            ForwardingGuard
+        }
+    }
+
+    impl Drop for ForwardingGuard {
+        fn drop(&mut self) {
+            FORWARDING.with(|forwarding| forwarding.set(false));
+        }
+    }
+
+    fn forward(env: &JNIEnv, target: &str, level: Level, message: &str) -> JniResult<()> {
+        let logger = logger_for(env, target)?;
+        let message = JObject::from(env.new_string(message)?);
+        let result = env.call_method_unchecked(
+            logger.as_obj(),
+            level_method_id(level),
+            JavaType::Primitive(Primitive::Void),
+            &[JValue::from(message)],
+        );
+        env.delete_local_ref(message)?;
+        result?.v()
+    }
+
+    fn logger_for(env: &JNIEnv, target: &str) -> JniResult<CountedRef> {
+        let mut loggers = LOGGERS.lock();
+        if let Some((_, logger)) = loggers.iter().find(|(cached, _)| cached == target) {
+            return Ok(logger.clone());
+        }
+        let log_manager = unsafe { cached_ref(&LOG_MANAGER) };
+        let name = JObject::from(env.new_string(target.replace("::", "."))?);
+        let logger = env
+            .call_static_method_unchecked(
+                &log_manager,
+                get_logger_id(),
+                JavaType::Object(LOGGER_CLASS.into()),
+                &[JValue::from(name)],
+            )?
+            .l()?;
+        env.delete_local_ref(name)?;
+        let global_ref = ref_metrics::new_global_ref(env, logger)?;
+        env.delete_local_ref(logger)?;
+        loggers.push((target.to_owned(), global_ref.clone()));
+        Ok(global_ref)
     }
 }
 
-/// Refers to the cached methods of the `com.exonum.binding.core.transaction.ExecutionException` class.
-pub mod execution_exception {
+/// Refers to the cached methods boxing and unboxing the primitive values
+/// (`java.lang.Integer`, `java.lang.Long` and `java.lang.Boolean`).
+pub mod boxing {
     use super::*;
+    use jni::signature::Primitive;
 
-    /// Returns cached `JMethodID` for `ExecutionException.getErrorCode()`.
-    pub fn get_error_code_id() -> JMethodID<'static> {
+    /// Returns cached `JMethodID` for `java.lang.Integer.intValue()`.
+    pub fn int_value_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { INTEGER_INT_VALUE })
+    }
+
+    /// Returns cached `JMethodID` for `java.lang.Long.longValue()`.
+    pub fn long_value_id() -> JMethodID<'static> {
+        check_cache_initialized();
+        cached_method_id(unsafe { LONG_LONG_VALUE })
+    }
+
+    /// Boxes the value into `java.lang.Integer`.
+    pub fn box_int<'a>(env: &JNIEnv<'a>, value: i32) -> JniResult<JObject<'a>> {
+        env.call_static_method_unchecked(
+            &classes_refs::java_lang_integer(),
+            static_methods::integer_value_of_id(),
+            JavaType::Object(JAVA_LANG_INTEGER_CLASS.into()),
+            &[JValue::from(value)],
+        )?
+        .l()
+    }
+
+    /// Boxes the value into `java.lang.Long`.
+    pub fn box_long<'a>(env: &JNIEnv<'a>, value: i64) -> JniResult<JObject<'a>> {
+        env.call_static_method_unchecked(
+            &classes_refs::java_lang_long(),
+            static_methods::long_value_of_id(),
+            JavaType::Object(JAVA_LANG_LONG_CLASS.into()),
+            &[JValue::from(value)],
+        )?
+        .l()
+    }
+
+    /// Boxes the value into `java.lang.Boolean`, returning a new local reference
+    /// to the cached `Boolean.TRUE` or `Boolean.FALSE` singleton instead of allocating.
+    pub fn boolean<'a>(env: &JNIEnv<'a>, value: bool) -> JniResult<JObject<'a>> {
+        check_cache_initialized();
+        let singleton = unsafe {
+            if value {
+                cached_ref(&BOOLEAN_TRUE)
+            } else {
+                cached_ref(&BOOLEAN_FALSE)
+            }
+        };
+        let raw = env.get_native_interface();
+        // Safe as the singleton is a valid global reference.
+        let local = unsafe { (**raw).NewLocalRef.unwrap()(raw, singleton.as_obj().into_inner()) };
+        if local.is_null() {
+            // `OutOfMemoryError` is thrown.
+            return Err(JniErrorKind::JavaException.into());
+        }
+        Ok(JObject::from(local))
+    }
+
+    /// Returns the value of the given `java.lang.Integer`.
+    ///
+    /// Returns an error if the object is null.
+    pub fn int_value(env: &JNIEnv, integer: JObject) -> JniResult<i32> {
+        if integer.is_null() {
+            return Err(JniErrorKind::NullPtr("Integer").into());
+        }
+        env.call_method_unchecked(
+            integer,
+            int_value_id(),
+            JavaType::Primitive(Primitive::Int),
+            &[],
+        )?
+        .i()
+    }
+
+    /// Returns the value of the given `java.lang.Long`.
+    ///
+    /// Returns an error if the object is null.
+    pub fn long_value(env: &JNIEnv, long: JObject) -> JniResult<i64> {
+        if long.is_null() {
+            return Err(JniErrorKind::NullPtr("Long").into());
+        }
+        env.call_method_unchecked(
+            long,
+            long_value_id(),
+            JavaType::Primitive(Primitive::Long),
+            &[],
+        )?
+        .j()
+    }
+}
+
+/// Refers to the cached static methods of the `java.lang.System` class.
+pub mod system {
+    use super::*;
+    use jni::signature::Primitive;
+
+    /// Returns the value of the JVM monotonic clock (`System.nanoTime()`) in nanoseconds,
+    /// allowing to compare the time measured on the native side with the JVM measurements.
+    pub fn nano_time(env: &JNIEnv) -> JniResult<i64> {
+        env.call_static_method_unchecked(
+            &classes_refs::java_lang_system(),
+            static_methods::system_nano_time_id(),
+            JavaType::Primitive(Primitive::Long),
+            &[],
+        )?
+        .j()
+    }
+}
+
+/// Refers to the cached fields of various classes.
+pub mod fields {
+    use super::*;
+
+    /// Returns cached `JFieldID` for `ExecutionException.errorCode`.
+    pub fn execution_exception_error_code_id() -> JFieldID<'static> {
         check_cache_initialized();
-        unsafe { EXECUTION_EXCEPTION_GET_ERROR_CODE.unwrap() }
+        unsafe { EXECUTION_EXCEPTION_ERROR_CODE.unwrap() }
     }
 }
 
@@ -326,33 +4040,285 @@ pub mod execution_exception {
 pub mod classes_refs {
     use super::*;
 
+    /// Returns cached `JClass` for `java/lang/Class` as a `GlobalRef`.
+    pub fn java_lang_class() -> GlobalRef {
+        check_cache_initialized();
+        unsafe { cached_ref(&JAVA_LANG_CLASS) }
+    }
+
     /// Returns cached `JClass` for `java/lang/Error` as a `GlobalRef`.
     pub fn java_lang_error() -> GlobalRef {
         check_cache_initialized();
-        unsafe { JAVA_LANG_ERROR.clone().unwrap() }
+        unsafe { cached_ref(&JAVA_LANG_ERROR) }
+    }
+
+    /// Returns cached `JClass` for `java/lang/Integer` as a `GlobalRef`.
+    pub fn java_lang_integer() -> GlobalRef {
+        check_cache_initialized();
+        unsafe { cached_ref(&JAVA_LANG_INTEGER) }
+    }
+
+    /// Returns cached `JClass` for `java/lang/Long` as a `GlobalRef`.
+    pub fn java_lang_long() -> GlobalRef {
+        check_cache_initialized();
+        unsafe { cached_ref(&JAVA_LANG_LONG) }
+    }
+
+    /// Returns cached `JClass` for `java/util/ArrayList` as a `GlobalRef`.
+    pub fn java_util_array_list() -> GlobalRef {
+        check_cache_initialized();
+        unsafe { cached_ref(&JAVA_UTIL_ARRAY_LIST) }
+    }
+
+    /// Returns cached `JClass` for `java/lang/System` as a `GlobalRef`.
+    pub fn java_lang_system() -> GlobalRef {
+        check_cache_initialized();
+        unsafe { cached_ref(&JAVA_LANG_SYSTEM) }
     }
 
     /// Returns cached `JClass` for `java/lang/RuntimeException` as a `GlobalRef`.
     pub fn java_lang_runtime_exception() -> GlobalRef {
         check_cache_initialized();
-        unsafe { JAVA_LANG_RUNTIME_EXCEPTION.clone().unwrap() }
+        unsafe { cached_ref(&JAVA_LANG_RUNTIME_EXCEPTION) }
     }
 
     /// Returns cached `JClass` for `java/lang/IllegalArgumentException` as a `GlobalRef`.
     pub fn java_lang_illegal_argument_exception() -> GlobalRef {
         check_cache_initialized();
-        unsafe { JAVA_LANG_ILLEGAL_ARGUMENT_EXCEPTION.clone().unwrap() }
+        unsafe { cached_ref(&JAVA_LANG_ILLEGAL_ARGUMENT_EXCEPTION) }
+    }
+
+    /// Returns cached `JClass` for `java/lang/IllegalStateException` as a `GlobalRef`.
+    pub fn java_lang_illegal_state_exception() -> GlobalRef {
+        check_cache_initialized();
+        unsafe { cached_ref(&JAVA_LANG_ILLEGAL_STATE_EXCEPTION) }
+    }
+
+    /// Returns cached `JClass` for `java/util/NoSuchElementException` as a `GlobalRef`.
+    pub fn java_util_no_such_element_exception() -> GlobalRef {
+        check_cache_initialized();
+        unsafe { cached_ref(&JAVA_UTIL_NO_SUCH_ELEMENT_EXCEPTION) }
+    }
+
+    /// Returns cached `JClass` for `java/lang/IndexOutOfBoundsException` as a `GlobalRef`.
+    pub fn java_lang_index_out_of_bounds_exception() -> GlobalRef {
+        check_cache_initialized();
+        unsafe { cached_ref(&JAVA_LANG_INDEX_OUT_OF_BOUNDS_EXCEPTION) }
     }
 
     /// Returns cached `JClass` for `ExecutionException` as a `GlobalRef`.
     pub fn execution_exception() -> GlobalRef {
         check_cache_initialized();
-        unsafe { EXECUTION_EXCEPTION.clone().unwrap() }
+        unsafe { cached_ref(&EXECUTION_EXCEPTION) }
     }
 
     /// Returns cached `JClass` for `UnexpectedExecutionException` as a `GlobalRef`.
     pub fn unexpected_execution_exception() -> GlobalRef {
         check_cache_initialized();
-        unsafe { UNEXPECTED_EXECUTION_EXCEPTION.clone().unwrap() }
+        unsafe { cached_ref(&UNEXPECTED_EXECUTION_EXCEPTION) }
+    }
+
+    /// Returns cached `JClass` for `KeyPair` as a `GlobalRef`.
+    pub fn key_pair() -> GlobalRef {
+        check_cache_initialized();
+        unsafe { cached_ref(&KEY_PAIR) }
+    }
+
+    /// Returns cached `JClass` for `ServiceRuntimeAdapter` as a `GlobalRef`.
+    pub fn service_runtime_adapter() -> GlobalRef {
+        check_cache_initialized();
+        unsafe { cached_ref(&SERVICE_RUNTIME_ADAPTER) }
+    }
+
+    /// Returns the JNI names and the cached `JClass`es of all cached exception classes.
+    ///
+    /// The subclasses precede their superclasses, so the first entry the throwable is
+    /// an instance of is the most derived cached class of it.
+    ///
+    /// The returned references are owned, so they remain valid if the cache is
+    /// reset later.
+    pub fn all() -> Vec<(&'static str, GlobalRef)> {
+        check_cache_initialized();
+        unsafe { EXCEPTION_CLASSES.clone() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+
+    /// Messages logged at error level.
+    static LOGGED_ERRORS: Mutex<Vec<String>> = const_mutex(Vec::new());
+    static LOGGER: ErrorCapturingLogger = ErrorCapturingLogger;
+
+    struct ErrorCapturingLogger;
+
+    impl Log for ErrorCapturingLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.level() == Level::Error
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                LOGGED_ERRORS.lock().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn on_load_panic_logs_payload() {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(LevelFilter::Error);
+
+        let version = catch_unwind(|| -> jint { panic!("Class Foo not found") })
+            .unwrap_or_else(|payload| on_load_panic(payload, None));
+        assert_eq!(version, INVALID_JNI_VERSION);
+
+        let backtrace = Some("at foo::bar".to_owned());
+        let version = catch_unwind(|| -> jint { panic!("Class Bar not found") })
+            .unwrap_or_else(|payload| on_load_panic(payload, backtrace));
+        assert_eq!(version, INVALID_JNI_VERSION);
+
+        let logged_errors = LOGGED_ERRORS.lock();
+        assert!(logged_errors
+            .iter()
+            .any(|message| message.contains("Class Foo not found")));
+        assert!(logged_errors
+            .iter()
+            .any(|message| message.contains("Class Bar not found\nat foo::bar")));
+    }
+
+    #[test]
+    fn caching_guard_resets_flag_on_panic() {
+        let result = catch_unwind(|| {
+            let _guard = CachingGuard::new();
+            assert!(CACHING_ON_THIS_THREAD.with(Cell::get));
+            panic!("Test panic");
+        });
+        assert!(result.is_err());
+        assert!(!CACHING_ON_THIS_THREAD.with(Cell::get));
+    }
+
+    #[test]
+    fn catch_unwind_with_backtrace_returns_payload() {
+        let (result, _backtrace) = catch_unwind_with_backtrace(|| panic!("Test panic"));
+        let payload = result.unwrap_err();
+        assert_eq!(any_to_string(&payload), "Test panic");
+
+        let (result, backtrace) = catch_unwind_with_backtrace(|| 1);
+        assert_eq!(result.unwrap(), 1);
+        assert!(backtrace.is_none());
+    }
+
+    #[test]
+    fn adapter_class_name_in_internal_form() {
+        assert_eq!(
+            to_internal_class_name("com.example.runtime.ServiceRuntimeAdapter"),
+            "com/example/runtime/ServiceRuntimeAdapter"
+        );
+        assert_eq!(
+            to_internal_class_name(" com/example/ServiceRuntimeAdapter "),
+            "com/example/ServiceRuntimeAdapter"
+        );
+    }
+
+    #[test]
+    fn jni_version_capped_at_max_version() {
+        assert_eq!(
+            cap_jni_version(JNI_VERSION_10, JNI_VERSION_1_8),
+            JNI_VERSION_1_8
+        );
+        assert_eq!(
+            cap_jni_version(JNI_VERSION_9, JNI_VERSION_10),
+            JNI_VERSION_9
+        );
+        assert_eq!(
+            cap_jni_version(JNI_VERSION_1_8, JNI_VERSION_1_8),
+            JNI_VERSION_1_8
+        );
+    }
+
+    #[test]
+    fn format_jni_versions() {
+        assert_eq!(format_jni_version(JNI_VERSION_1_8), "1.8");
+        assert_eq!(format_jni_version(JNI_VERSION_9), "9");
+        assert_eq!(format_jni_version(JNI_VERSION_10), "10");
+    }
+
+    #[test]
+    fn all_methods_required_by_default() {
+        let required = RequiredMethods(None);
+        assert!(required.is_required("initialize"));
+        assert!(required.is_required("shutdown"));
+    }
+
+    #[test]
+    fn parse_required_methods() {
+        let required = RequiredMethods::parse(" initialize, executeTransaction,,");
+        assert!(required.is_required("initialize"));
+        assert!(required.is_required("executeTransaction"));
+        assert!(!required.is_required("shutdown"));
+        assert!(!required.is_required(""));
+    }
+
+    #[test]
+    fn contract_mismatch_lists_methods() {
+        let mismatch = ContractMismatch {
+            class: "com/example/Adapter".to_owned(),
+            class_not_found: false,
+            missing: vec![("initialize".to_owned(), "(J)V".to_owned())],
+            mismatched: vec![("shutdown".to_owned(), "()V".to_owned())],
+        };
+        let message = mismatch.to_string();
+        assert!(message.starts_with("Class com/example/Adapter does not match"));
+        assert!(message.contains("missing methods: initialize(J)V"));
+        assert!(message.contains("methods with unexpected signatures: shutdown()V"));
+    }
+
+    #[cfg(feature = "mock-cache")]
+    #[test]
+    fn force_initialized_for_tests_sets_method_ids() {
+        force_initialized_for_tests();
+        assert!(is_cache_initialized());
+        assert!(!object::to_string_id().into_inner().is_null());
+        assert!(!runtime_adapter::execute_tx_id().into_inner().is_null());
+        assert!(!runtime_adapter::initiate_resuming_service_id()
+            .into_inner()
+            .is_null());
+
+        reset_cache();
+        assert!(!is_cache_initialized());
+    }
+
+    #[test]
+    fn set_cache_failure_policy() {
+        assert_eq!(on_cache_failure(), FailurePolicy::default());
+        assert_eq!(
+            FailurePolicy::default(),
+            FailurePolicy::ReturnInvalidVersion
+        );
+
+        set_on_cache_failure(FailurePolicy::AbortProcess);
+        assert_eq!(on_cache_failure(), FailurePolicy::AbortProcess);
+
+        set_on_cache_failure(FailurePolicy::ReturnInvalidVersion);
+        assert_eq!(on_cache_failure(), FailurePolicy::ReturnInvalidVersion);
+    }
+
+    #[test]
+    fn parse_known_jni_versions() {
+        assert_eq!(parse_jni_version("1.8"), Some(JNI_VERSION_1_8));
+        assert_eq!(parse_jni_version("9"), Some(JNI_VERSION_9));
+        assert_eq!(parse_jni_version(" 10 "), Some(JNI_VERSION_10));
+    }
+
+    #[test]
+    fn parse_unknown_jni_versions() {
+        assert_eq!(parse_jni_version(""), None);
+        assert_eq!(parse_jni_version("1.6"), None);
+        assert_eq!(parse_jni_version("11"), None);
     }
 }
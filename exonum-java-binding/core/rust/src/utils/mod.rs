@@ -17,20 +17,32 @@
 #![deny(non_snake_case)]
 
 pub use self::conversion::{
-    convert_hash, convert_to_hash, convert_to_index_address, convert_to_string,
-    java_arrays_to_rust, optional_array_to_java, proto_to_java_bytes,
+    convert_hash, convert_to_bytes, convert_to_hash, convert_to_index_address, convert_to_string,
+    format_artifact_coordinate, format_artifact_id, java_arrays_to_rust, optional_array_to_java,
+    proto_to_java_bytes, ByteArrayError,
 };
 pub use self::errors::{
-    any_to_string, check_error_on_exception, describe_java_exception, get_and_clear_java_exception,
-    get_exception_cause, panic_on_exception, unwrap_exc_or, unwrap_exc_or_default, unwrap_jni,
-    unwrap_jni_verbose,
+    any_to_string, capture_stack_trace, capture_stack_trace_with_limit, check_error_on_exception,
+    classify_exception, describe_java_exception, describe_throwable, describe_throwable_localized,
+    describe_throwable_simple, describe_throwable_with_depth, get_and_clear_java_exception,
+    get_exception_cause, panic_on_exception, throw_for_kind, throw_illegal_state, throw_rust_panic,
+    unwrap_exc_or, unwrap_exc_or_default, unwrap_jni, unwrap_jni_verbose, ExceptionKind,
+    JavaErrorKind, DEFAULT_MAX_CAUSE_DEPTH, DEFAULT_MAX_STACK_FRAMES,
+};
+pub use self::jni::{
+    describe_object, get_class_name, get_class_simple_name, get_exception_localized_message,
+    get_exception_message, is_assignable_from, is_instance_of_cached, with_local_frame,
+    DEFAULT_LOCAL_FRAME_CAPACITY,
 };
-pub use self::jni::{get_class_name, get_exception_message};
 
+pub mod byte_array;
+pub mod byte_buffer;
 mod conversion;
 mod errors;
 mod jni;
 pub mod jni_cache;
+pub mod ref_metrics;
+pub mod weak_ref;
 
 /// Asserts that given closure panics while executed and the resulting error message contains given
 /// substring.
@@ -0,0 +1,89 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Counts the global references held by the native library (the cached classes, the proxies
+//! of Java objects, etc.) to help diagnosing global reference leaks during long runs.
+//! The references are counted only if "ref-metrics" feature is enabled.
+
+use jni::{
+    objects::{GlobalRef, JObject},
+    JNIEnv,
+};
+
+use crate::JniResult;
+
+use std::{
+    ops::Deref,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
+};
+
+static LIVE_GLOBAL_REFS: AtomicI64 = AtomicI64::new(0);
+
+/// Creates a new global reference to the given object, counted as held by the native
+/// library until the returned wrapper and all its clones are dropped.
+pub fn new_global_ref(env: &JNIEnv, obj: JObject) -> JniResult<CountedRef> {
+    let global = env.new_global_ref(obj)?;
+    Ok(CountedRef {
+        global,
+        _count: Arc::new(LiveRef::acquire()),
+    })
+}
+
+/// A `GlobalRef` counted as held by the native library (see `live_global_ref_count`).
+///
+/// The clones share the count, as they share the global reference. The `GlobalRef`s
+/// cloned from the wrapper are not counted, so they shall not outlive it.
+#[derive(Clone)]
+pub struct CountedRef {
+    global: GlobalRef,
+    _count: Arc<LiveRef>,
+}
+
+impl Deref for CountedRef {
+    type Target = GlobalRef;
+
+    fn deref(&self) -> &GlobalRef {
+        &self.global
+    }
+}
+
+/// Counts a global reference from its creation till drop.
+struct LiveRef;
+
+impl LiveRef {
+    fn acquire() -> Self {
+        if cfg!(feature = "ref-metrics") {
+            LIVE_GLOBAL_REFS.fetch_add(1, Ordering::Relaxed);
+        }
+        LiveRef
+    }
+}
+
+impl Drop for LiveRef {
+    fn drop(&mut self) {
+        if cfg!(feature = "ref-metrics") {
+            LIVE_GLOBAL_REFS.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Returns the number of global references held by the native library.
+///
+/// Always returns zero unless "ref-metrics" feature is enabled.
+pub fn live_global_ref_count() -> i64 {
+    LIVE_GLOBAL_REFS.load(Ordering::Relaxed)
+}
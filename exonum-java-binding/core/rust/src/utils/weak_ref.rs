@@ -0,0 +1,94 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weak global references, which refer to Java objects without preventing their garbage
+//! collection, e.g., to the objects of the services that may be stopped.
+
+use jni::{objects::JObject, sys::jweak, JNIEnv, JavaVM};
+use log::error;
+
+use crate::{
+    utils::ref_metrics::{self, CountedRef},
+    JniErrorKind, JniResult,
+};
+
+/// A weak global reference to a Java object.
+///
+/// Unlike `GlobalRef`, does not keep the object alive. Can be shared between threads;
+/// the reference is deleted when dropped.
+pub struct WeakRef {
+    weak: jweak,
+    vm: JavaVM,
+}
+
+// Weak global references are valid in any thread until deleted.
+unsafe impl Send for WeakRef {}
+unsafe impl Sync for WeakRef {}
+
+impl Drop for WeakRef {
+    fn drop(&mut self) {
+        let delete = |env: &JNIEnv| {
+            let raw = env.get_native_interface();
+            // Safe as the reference is valid until this call.
+            unsafe { (**raw).DeleteWeakGlobalRef.unwrap()(raw, self.weak) };
+        };
+        match self.vm.get_env() {
+            Ok(env) => delete(&env),
+            Err(_) => match self.vm.attach_current_thread() {
+                Ok(env) => delete(&env),
+                Err(e) => error!("Failed to delete a weak global reference: {}", e),
+            },
+        }
+    }
+}
+
+/// Creates a weak global reference to the given object.
+///
+/// Returns an error for the null object.
+pub fn new_weak_ref(env: &JNIEnv, obj: JObject) -> JniResult<WeakRef> {
+    if obj.is_null() {
+        return Err(JniErrorKind::NullPtr("Object").into());
+    }
+    let raw = env.get_native_interface();
+    // Safe as the object is a valid non-null reference.
+    let weak = unsafe { (**raw).NewWeakGlobalRef.unwrap()(raw, obj.into_inner()) };
+    if weak.is_null() {
+        // `OutOfMemoryError` is thrown.
+        return Err(JniErrorKind::JavaException.into());
+    }
+    Ok(WeakRef {
+        weak,
+        vm: env.get_java_vm()?,
+    })
+}
+
+/// Returns a (strong) global reference to the object the weak reference refers to,
+/// keeping the object alive while the returned reference is held.
+///
+/// Returns `None` if the object was garbage collected. The returned reference is counted
+/// as held by the native library (see `ref_metrics`).
+pub fn upgrade_weak(env: &JNIEnv, weak: &WeakRef) -> JniResult<Option<CountedRef>> {
+    let obj = JObject::from(weak.weak);
+    if env.is_same_object(obj, JObject::null())? {
+        return Ok(None);
+    }
+    // The object may be collected after the check, in which case the returned
+    // reference is null.
+    let global_ref = ref_metrics::new_global_ref(env, obj)?;
+    if global_ref.as_obj().is_null() {
+        Ok(None)
+    } else {
+        Ok(Some(global_ref))
+    }
+}
@@ -307,6 +307,15 @@ public class ServiceRuntimeAdapter {
     serviceRuntime.shutdown();
   }
 
+  /**
+   * Verifies that the native library caches of Java classes and methods are initialized
+   * and that each cached entry can still be resolved.
+   *
+   * @return a report of the check, with a summary line followed by a line per entry
+   *     that failed to resolve
+   */
+  static native String nativeSelfTest();
+
   private static void handleCloseFailure(CloseFailuresException e) throws CloseFailuresException {
     logger.error("Failed to close some resources", e);
     throw e;